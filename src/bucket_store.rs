@@ -0,0 +1,343 @@
+//! Disk-backed, memory-mapped corpus store with power-of-two bucket sharding.
+//!
+//! Entries are partitioned across `2^bucket_bits` shard files chosen by the
+//! high bits of a hash of the entry's index. Each shard is an mmap of
+//! fixed-size cells (`CELL_SIZE` bytes: an occupancy/uid tag plus the
+//! scalar metadata fields and an offset/length into a companion "data
+//! bucket" file that holds the variable-length `js_code` bytes). Insertion
+//! linearly probes up to `MAX_SEARCH` consecutive cells before signalling
+//! that the shard needs to grow, at which point its cell capacity is
+//! doubled and its live entries are rehashed into the new file.
+//!
+//! `CorpusManager` mirrors every entry's scalar metadata and `js_code` here
+//! when a store is configured, then drops its own in-memory copy of
+//! `js_code` (see `CorpusManager::materialize`) -- the point being that a
+//! worker's resident `VecDeque<CorpusEntry>` stays bounded by scalar fields
+//! plus `program_ir`, with the (typically much larger) `js_code` bytes
+//! living on disk and paged back in through `bucket_store_get` only for the
+//! one entry actually selected, instead of every entry's full text sitting
+//! in RAM all the time.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use memmap2::{MmapMut, MmapOptions};
+
+const CELL_SIZE: usize = 64;
+const MAX_SEARCH: usize = 8;
+const INITIAL_CELLS: usize = 64;
+
+/// The scalar metadata fields a cell's header stores alongside the
+/// js_code offset/length, mirroring what `update_entry_error`/
+/// `update_entry_timeout` and the scoring scans read and write today.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntryMeta {
+    pub times_used: u32,
+    pub success_count: u32,
+    pub coverage_found: u32,
+    pub error_count: u32,
+    pub timeout_count: u32,
+    pub performance_score: f64,
+}
+
+impl EntryMeta {
+    fn write_into(&self, cell: &mut [u8]) {
+        cell[9..13].copy_from_slice(&self.times_used.to_le_bytes());
+        cell[13..17].copy_from_slice(&self.success_count.to_le_bytes());
+        cell[17..21].copy_from_slice(&self.coverage_found.to_le_bytes());
+        cell[21..25].copy_from_slice(&self.error_count.to_le_bytes());
+        cell[25..29].copy_from_slice(&self.timeout_count.to_le_bytes());
+        cell[29..37].copy_from_slice(&self.performance_score.to_le_bytes());
+    }
+
+    fn read_from(cell: &[u8]) -> Self {
+        EntryMeta {
+            times_used: u32::from_le_bytes(cell[9..13].try_into().unwrap()),
+            success_count: u32::from_le_bytes(cell[13..17].try_into().unwrap()),
+            coverage_found: u32::from_le_bytes(cell[17..21].try_into().unwrap()),
+            error_count: u32::from_le_bytes(cell[21..25].try_into().unwrap()),
+            timeout_count: u32::from_le_bytes(cell[25..29].try_into().unwrap()),
+            performance_score: f64::from_le_bytes(cell[29..37].try_into().unwrap()),
+        }
+    }
+}
+
+fn hash_index(index: u32) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    index.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// What `Bucket::put` tells the caller when every probed cell was occupied
+/// by a different key: the shard is full and must grow before retrying.
+enum PutOutcome {
+    Done,
+    NeedsGrow,
+}
+
+struct Bucket {
+    shard_id: usize,
+    root: PathBuf,
+    index_path: PathBuf,
+    index_file: File,
+    index_mmap: MmapMut,
+    num_cells: usize,
+    data_path: PathBuf,
+    data_file: File,
+    data_len: u64,
+    data_capacity: u64,
+}
+
+impl Bucket {
+    fn cell_offset(&self, cell: usize) -> usize {
+        cell * CELL_SIZE
+    }
+
+    fn open(root: &Path, shard_id: usize, num_cells: usize) -> io::Result<Self> {
+        let index_path = root.join(format!("shard_{}.idx", shard_id));
+        let data_path = root.join(format!("shard_{}.data", shard_id));
+
+        let index_file = OpenOptions::new().read(true).write(true).create(true).open(&index_path)?;
+        let required_len = (num_cells * CELL_SIZE) as u64;
+        if index_file.metadata()?.len() < required_len {
+            index_file.set_len(required_len)?;
+        }
+        let index_mmap = unsafe { MmapOptions::new().map_mut(&index_file)? };
+
+        let data_file = OpenOptions::new().read(true).write(true).create(true).open(&data_path)?;
+        let data_len = data_file.metadata()?.len();
+        let data_capacity = data_len.next_power_of_two().max(4096);
+        if data_len < data_capacity {
+            data_file.set_len(data_capacity)?;
+        }
+
+        Ok(Bucket {
+            shard_id,
+            root: root.to_path_buf(),
+            index_path,
+            index_file,
+            index_mmap,
+            num_cells,
+            data_path,
+            data_file,
+            data_len,
+            data_capacity,
+        })
+    }
+
+    fn cell(&self, cell: usize) -> &[u8] {
+        let off = self.cell_offset(cell);
+        &self.index_mmap[off..off + CELL_SIZE]
+    }
+
+    fn cell_mut(&mut self, cell: usize) -> &mut [u8] {
+        let off = self.cell_offset(cell);
+        &mut self.index_mmap[off..off + CELL_SIZE]
+    }
+
+    fn is_occupied(cell: &[u8]) -> bool {
+        cell[0] == 1
+    }
+
+    fn cell_uid(cell: &[u8]) -> u32 {
+        u32::from_le_bytes(cell[1..5].try_into().unwrap())
+    }
+
+    /// Appends `js_code` to the data file, growing its capacity to the
+    /// next power-of-two of the new length if needed.
+    fn append_data(&mut self, js_code: &[u8]) -> io::Result<(u64, u64)> {
+        use std::io::{Seek, SeekFrom, Write};
+
+        let offset = self.data_len;
+        let new_len = offset + js_code.len() as u64;
+        if new_len > self.data_capacity {
+            self.data_capacity = new_len.next_power_of_two();
+            self.data_file.set_len(self.data_capacity)?;
+        }
+        self.data_file.seek(SeekFrom::Start(offset))?;
+        self.data_file.write_all(js_code)?;
+        self.data_len = new_len;
+        Ok((offset, js_code.len() as u64))
+    }
+
+    fn read_data(&self, offset: u64, len: u64) -> io::Result<Vec<u8>> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let mut file = OpenOptions::new().read(true).open(&self.data_path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut buf = vec![0u8; len as usize];
+        file.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Linearly probes up to `MAX_SEARCH` cells starting at `start`,
+    /// writing into the first empty or matching-uid cell.
+    fn put(&mut self, index: u32, start: usize, meta: EntryMeta, js_code: &[u8]) -> io::Result<PutOutcome> {
+        let (data_offset, data_len) = self.append_data(js_code)?;
+        for probe in 0..MAX_SEARCH {
+            let cell_idx = (start + probe) % self.num_cells;
+            let occupied = Self::is_occupied(self.cell(cell_idx));
+            let matches = occupied && Self::cell_uid(self.cell(cell_idx)) == index;
+            if !occupied || matches {
+                let cell = self.cell_mut(cell_idx);
+                cell[0] = 1;
+                cell[1..5].copy_from_slice(&index.to_le_bytes());
+                meta.write_into(cell);
+                cell[37..45].copy_from_slice(&data_offset.to_le_bytes());
+                cell[45..53].copy_from_slice(&data_len.to_le_bytes());
+                return Ok(PutOutcome::Done);
+            }
+        }
+        Ok(PutOutcome::NeedsGrow)
+    }
+
+    /// Rewrites a cell's scalar metadata fields in place, leaving its
+    /// occupancy tag and data offset/length untouched. Used for
+    /// `update_entry_error`/`update_entry_timeout`, which only touch
+    /// metadata and must not require the (possibly large) `js_code` bytes
+    /// just to bump an error counter.
+    fn update_meta(&mut self, index: u32, start: usize, meta: EntryMeta) -> bool {
+        for probe in 0..MAX_SEARCH {
+            let cell_idx = (start + probe) % self.num_cells;
+            let cell = self.cell(cell_idx);
+            if Self::is_occupied(cell) && Self::cell_uid(cell) == index {
+                meta.write_into(self.cell_mut(cell_idx));
+                return true;
+            }
+        }
+        false
+    }
+
+    fn get(&self, index: u32, start: usize) -> io::Result<Option<(EntryMeta, Vec<u8>)>> {
+        for probe in 0..MAX_SEARCH {
+            let cell_idx = (start + probe) % self.num_cells;
+            let cell = self.cell(cell_idx);
+            if Self::is_occupied(cell) && Self::cell_uid(cell) == index {
+                let meta = EntryMeta::read_from(cell);
+                let data_offset = u64::from_le_bytes(cell[37..45].try_into().unwrap());
+                let data_len = u64::from_le_bytes(cell[45..53].try_into().unwrap());
+                return Ok(Some((meta, self.read_data(data_offset, data_len)?)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn live_cells(&self) -> Vec<(u32, EntryMeta, u64, u64)> {
+        let mut out = Vec::new();
+        for cell_idx in 0..self.num_cells {
+            let cell = self.cell(cell_idx);
+            if Self::is_occupied(cell) {
+                let index = Self::cell_uid(cell);
+                let meta = EntryMeta::read_from(cell);
+                let data_offset = u64::from_le_bytes(cell[37..45].try_into().unwrap());
+                let data_len = u64::from_le_bytes(cell[45..53].try_into().unwrap());
+                out.push((index, meta, data_offset, data_len));
+            }
+        }
+        out
+    }
+
+    /// Doubles this shard's cell capacity and rehashes every live entry
+    /// into the grown index file. The data file (and its offsets) are
+    /// untouched since only the index layout changes.
+    fn grow(&mut self) -> io::Result<()> {
+        let live = self.live_cells();
+        let new_num_cells = self.num_cells * 2;
+
+        drop(std::mem::replace(&mut self.index_mmap, unsafe {
+            MmapOptions::new().map_mut(&self.index_file)?
+        }));
+        self.index_file.set_len((new_num_cells * CELL_SIZE) as u64)?;
+        self.index_mmap = unsafe { MmapOptions::new().map_mut(&self.index_file)? };
+        for byte in self.index_mmap.iter_mut() {
+            *byte = 0;
+        }
+        self.num_cells = new_num_cells;
+
+        for (index, meta, data_offset, data_len) in live {
+            let start = (hash_index(index) as usize) % self.num_cells;
+            for probe in 0..MAX_SEARCH {
+                let cell_idx = (start + probe) % self.num_cells;
+                if !Self::is_occupied(self.cell(cell_idx)) {
+                    let cell = self.cell_mut(cell_idx);
+                    cell[0] = 1;
+                    cell[1..5].copy_from_slice(&index.to_le_bytes());
+                    meta.write_into(cell);
+                    cell[37..45].copy_from_slice(&data_offset.to_le_bytes());
+                    cell[45..53].copy_from_slice(&data_len.to_le_bytes());
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Top-level handle: owns one `Bucket` per shard and routes puts/gets to
+/// the shard chosen by the high `bucket_bits` bits of `hash_index`.
+pub struct BucketStore {
+    root: PathBuf,
+    bucket_bits: u32,
+    buckets: Vec<Bucket>,
+}
+
+impl BucketStore {
+    pub fn open(root: &Path, bucket_bits: u32) -> io::Result<Self> {
+        std::fs::create_dir_all(root)?;
+        let num_shards = 1usize << bucket_bits;
+        let mut buckets = Vec::with_capacity(num_shards);
+        for shard_id in 0..num_shards {
+            buckets.push(Bucket::open(root, shard_id, INITIAL_CELLS)?);
+        }
+        Ok(BucketStore { root: root.to_path_buf(), bucket_bits, buckets })
+    }
+
+    fn shard_for(&self, index: u32) -> usize {
+        let hash = hash_index(index);
+        let shift = 64 - self.bucket_bits;
+        ((hash >> shift) as usize) % self.buckets.len()
+    }
+
+    pub fn put(&mut self, index: u32, meta: EntryMeta, js_code: &[u8]) -> io::Result<()> {
+        let shard_id = self.shard_for(index);
+        loop {
+            let start = (hash_index(index) as usize) % self.buckets[shard_id].num_cells;
+            match self.buckets[shard_id].put(index, start, meta, js_code)? {
+                PutOutcome::Done => return Ok(()),
+                PutOutcome::NeedsGrow => self.buckets[shard_id].grow()?,
+            }
+        }
+    }
+
+    /// Updates an entry's metadata in place if it's already present in the
+    /// store; a no-op (returns `false`) if the entry was never `put` here.
+    pub fn update_meta(&mut self, index: u32, meta: EntryMeta) -> bool {
+        let shard_id = self.shard_for(index);
+        let start = (hash_index(index) as usize) % self.buckets[shard_id].num_cells;
+        self.buckets[shard_id].update_meta(index, start, meta)
+    }
+
+    pub fn get(&self, index: u32) -> io::Result<Option<(EntryMeta, Vec<u8>)>> {
+        let shard_id = self.shard_for(index);
+        let bucket = &self.buckets[shard_id];
+        let start = (hash_index(index) as usize) % bucket.num_cells;
+        bucket.get(index, start)
+    }
+
+    /// Every live `(index, meta)` pair across all shards, for statistics
+    /// scans that need to iterate the whole store without reading any
+    /// `js_code` bytes from the data files.
+    pub fn live_entries(&self) -> Vec<(u32, EntryMeta)> {
+        self.buckets
+            .iter()
+            .flat_map(|b| b.live_cells().into_iter().map(|(idx, meta, _, _)| (idx, meta)))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.buckets.iter().map(|b| b.live_cells().len()).sum()
+    }
+}
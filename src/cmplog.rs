@@ -0,0 +1,205 @@
+use crate::coverage::{cov_clear_cmp_events, cov_fetch_cmp_events, fetch_event_count, CmpEvent};
+use std::collections::HashSet;
+
+/// Two operands observed at the same comparison site are worth recording as
+/// an input-to-state mutation target if they're either the same "shape"
+/// (fit in the same number of bytes) or within a small delta — both are
+/// patterns a single mutation step plausibly produced without the branch
+/// actually being informative yet, unlike two wildly different magnitudes.
+const MAX_DELTA: i64 = 4096;
+
+fn byte_width(value: i64) -> u32 {
+    let magnitude = value.unsigned_abs();
+    if magnitude == 0 {
+        1
+    } else {
+        ((64 - magnitude.leading_zeros()) as u32).div_ceil(8).max(1)
+    }
+}
+
+fn is_close(left: i64, right: i64) -> bool {
+    if left == right {
+        return false;
+    }
+    if byte_width(left) == byte_width(right) {
+        return true;
+    }
+    left.checked_sub(right)
+        .map(|delta| delta.abs() <= MAX_DELTA)
+        .unwrap_or(false)
+}
+
+/// One worker's table of `(observed, flips-to)` operand pairs harvested from
+/// `CmpEvent`s, used to drive input-to-state literal substitution. Both
+/// directions of every close pair are recorded since a literal could match
+/// either side of the comparison it feeds.
+#[derive(Default)]
+pub struct CmpTable {
+    pairs: HashSet<(i64, i64)>,
+}
+
+impl CmpTable {
+    /// Cap on recorded pairs so a worker that spends a long time on one
+    /// comparison-heavy program doesn't grow this without bound.
+    const MAX_PAIRS: usize = 4096;
+
+    pub fn record(&mut self, left: i64, right: i64) {
+        if !is_close(left, right) || self.pairs.len() >= Self::MAX_PAIRS {
+            return;
+        }
+        self.pairs.insert((left, right));
+        self.pairs.insert((right, left));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    pub fn pairs(&self) -> impl Iterator<Item = &(i64, i64)> {
+        self.pairs.iter()
+    }
+}
+
+/// Clears stale `CmpEvent`s, since the native side only ever appends to the
+/// buffer. Must be called immediately before any `execute_script` whose
+/// comparisons are meant to be attributed to that run and not a leftover
+/// one.
+pub fn clear_events(worker_id: usize) {
+    unsafe {
+        cov_clear_cmp_events(worker_id as i32);
+    }
+}
+
+/// Reads back every `CmpEvent` recorded since the last `clear_events`.
+pub fn fetch_events(worker_id: usize) -> Vec<CmpEvent> {
+    unsafe {
+        let count = fetch_event_count(worker_id as i32) as usize;
+        if count == 0 {
+            return Vec::new();
+        }
+        let ptr = cov_fetch_cmp_events(worker_id as i32);
+        if ptr.is_null() {
+            return Vec::new();
+        }
+        std::slice::from_raw_parts(ptr, count)
+            .iter()
+            .map(|e| CmpEvent { left: e.left, right: e.right })
+            .collect()
+    }
+}
+
+/// Runs `js_code`, recording whatever `CmpEvent`s it produces into `table`.
+/// Clears the event buffer both immediately before and after the execution
+/// so events never leak into (or out of) an unrelated run.
+pub fn record_from_execution(table: &mut CmpTable, worker_id: usize, js_code: &str) -> i32 {
+    clear_events(worker_id);
+    let result =
+        unsafe { crate::execute_script(js_code.as_ptr() as *mut i8, crate::MAX_TIMEOUT, 0, worker_id as i32) };
+    for event in fetch_events(worker_id) {
+        table.record(event.left, event.right);
+    }
+    clear_events(worker_id);
+    result
+}
+
+/// A numeric literal found in `js_code`, with its byte offsets so it can be
+/// substituted in place.
+struct NumericLiteral {
+    start: usize,
+    end: usize,
+    value: i64,
+}
+
+fn is_ident_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$'
+}
+
+fn find_numeric_literals(js_code: &str) -> Vec<NumericLiteral> {
+    let bytes = js_code.as_bytes();
+    let mut literals = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let starts_number = c.is_ascii_digit() || (c == b'-' && i + 1 < bytes.len() && bytes[i + 1].is_ascii_digit());
+        let preceded_by_ident = i > 0 && is_ident_char(bytes[i - 1]);
+        if starts_number && !preceded_by_ident {
+            let start = i;
+            let mut end = i + 1;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+            }
+            if let Ok(value) = js_code[start..end].parse::<i64>() {
+                literals.push(NumericLiteral { start, end, value });
+            }
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    literals
+}
+
+/// Input-to-state mutation: for every numeric literal in `js_code` that
+/// matches one side of a recorded `CmpTable` pair, substitute the other
+/// side so the comparison it feeds is more likely to flip. Returns one
+/// candidate per matching literal/pair rather than applying every
+/// substitution at once, mirroring RedQueen/LibAFL's one-substitution-per-
+/// candidate approach so a caller can test each in isolation against real
+/// coverage.
+pub fn input_to_state_candidates(table: &CmpTable, js_code: &str) -> Vec<String> {
+    if table.is_empty() {
+        return Vec::new();
+    }
+    let literals = find_numeric_literals(js_code);
+    let mut candidates = Vec::new();
+    for literal in &literals {
+        for (from, to) in table.pairs() {
+            if *from == literal.value {
+                let mut mutated = String::with_capacity(js_code.len());
+                mutated.push_str(&js_code[..literal.start]);
+                mutated.push_str(&to.to_string());
+                mutated.push_str(&js_code[literal.end..]);
+                candidates.push(mutated);
+            }
+        }
+    }
+    candidates
+}
+
+/// Colorization: perturbs one numeric literal at a time and re-executes,
+/// diffing the `CmpEvent`s each perturbation produces against `baseline` to
+/// find which comparisons a given literal actually feeds. A comparison that
+/// only appears (or changes) after perturbing literal `i` is attributed
+/// back to it, which lets a caller narrow `input_to_state_candidates` to
+/// substitutions that are data-flow-connected to a real comparison instead
+/// of matching on value alone.
+pub fn colorize(worker_id: usize, js_code: &str, baseline: &[CmpEvent]) -> Vec<(usize, Vec<CmpEvent>)> {
+    let literals = find_numeric_literals(js_code);
+    let baseline_set: HashSet<(i64, i64)> = baseline.iter().map(|e| (e.left, e.right)).collect();
+    let mut attributed = Vec::new();
+
+    for (idx, literal) in literals.iter().enumerate() {
+        let perturbed_value = literal.value.wrapping_add(0x1337);
+        let mut perturbed_code = String::with_capacity(js_code.len());
+        perturbed_code.push_str(&js_code[..literal.start]);
+        perturbed_code.push_str(&perturbed_value.to_string());
+        perturbed_code.push_str(&js_code[literal.end..]);
+
+        clear_events(worker_id);
+        unsafe {
+            crate::execute_script(perturbed_code.as_ptr() as *mut i8, crate::MAX_TIMEOUT, 0, worker_id as i32);
+        }
+        let events = fetch_events(worker_id);
+        clear_events(worker_id);
+
+        let changed: Vec<CmpEvent> = events
+            .into_iter()
+            .filter(|e| !baseline_set.contains(&(e.left, e.right)))
+            .collect();
+        if !changed.is_empty() {
+            attributed.push((idx, changed));
+        }
+    }
+
+    attributed
+}
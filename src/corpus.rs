@@ -5,9 +5,64 @@ use std::hash::{Hash, Hasher};
 use std::collections::{HashSet};
 use std::collections::hash_map::DefaultHasher;
 use std::fs::OpenOptions;
-use std::io::Write;
+use std::io::{Read, Write};
+use std::io;
+use std::path::PathBuf;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::sync::{Arc, Mutex};
+use crc32c;
+use sha2;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use crate::bucket_store::{BucketStore, EntryMeta};
 use crate::corpus_aspect::{BytecodeAnalysis, BytecodeCollector};
 
+/// Stable content hash used to correlate a parent entry across the worker
+/// that discovered a mutation and the master's own corpus index space.
+pub fn hash_str(s: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Strips whitespace and `//`/`/* */` comments from `program_ir` before
+/// hashing it for dedup, so cosmetically different but semantically
+/// identical seeds (different indentation, a trailing comment) collapse to
+/// the same hash instead of both being kept.
+fn normalize_program_ir(ir: &str) -> String {
+    let mut normalized = String::with_capacity(ir.len());
+    let bytes = ir.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'/' && i + 1 < bytes.len() && bytes[i + 1] == b'/' {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+        } else if bytes[i] == b'/' && i + 1 < bytes.len() && bytes[i + 1] == b'*' {
+            i += 2;
+            while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                i += 1;
+            }
+            i = (i + 2).min(bytes.len());
+        } else if bytes[i].is_ascii_whitespace() {
+            i += 1;
+        } else {
+            normalized.push(bytes[i] as char);
+            i += 1;
+        }
+    }
+    normalized
+}
+
+/// Content hash of `program_ir` used for corpus deduplication: normalizes
+/// away whitespace/comments first so the hash reflects the program's
+/// semantics rather than its formatting.
+fn dedup_hash(program_ir: &str) -> u64 {
+    hash_str(&normalize_program_ir(program_ir))
+}
+
 #[derive(Clone)]
 pub struct CorpusEntry {
     pub index: u32,
@@ -28,7 +83,277 @@ pub struct CorpusEntry {
     pub module_features: HashMap<usize, HashSet<u64>>,  // Track features hit per module
     pub bytecode_analysis: Option<BytecodeAnalysis>,  // Store bytecode analysis if available
     pub has_novel_bytecode: bool,  // Flag indicating if this entry has novel bytecode patterns
+    pub parent_index: Option<u32>,  // Index of the entry this one was derived from, if any
+    pub discovered_by_pass: Option<String>,  // Name of the mutation pass that produced this entry
+    pub exec_time_micros: u64,  // How long executing this entry's js_code took, for the minimizer scheduler
+    pub is_favored: bool,  // Whether this entry is the cheapest known owner of at least one edge
+    pub cached_score: f64,  // compute_score(self), kept up to date incrementally by the priority scheduler
+    pub logical_js_len: usize,  // js_code.len() at the time it was stored, kept accurate even after StorageMode::LessMemory clears js_code
+    pub logical_ir_len: usize,  // program_ir.len() at the time it was stored, same purpose as logical_js_len
+    pub checksum: String,  // hex digest of the on-disk .js bytes (ciphertext when encrypted) from the sidecar, set by load_from_disk; empty for entries not loaded from disk
+
+}
+
+/// Sum over an entry's covered features of `1 / max(1, global hit count)`:
+/// high when the entry exercises edges few other corpus entries hit, low
+/// when it only hits common ones. Drives the rarity term in `compute_score`.
+/// `global_edge_hits` is keyed by edge id (see `CorpusManager::global_edge_hits`),
+/// not by corpus entry index.
+fn rarity(entry: &CorpusEntry, global_edge_hits: &HashMap<u64, u64>) -> f64 {
+    entry
+        .feature_frequency
+        .keys()
+        .map(|feature| 1.0 / global_edge_hits.get(feature).copied().unwrap_or(1).max(1) as f64)
+        .sum()
+}
+
+/// The same multiplicative score `select_next_input` has always used:
+/// performance_score, scaled by code-size, success/coverage rewards, and
+/// error/timeout/usage penalties, plus a rarity term (AFL-style "keep the
+/// smallest seed per rare edge": exercising globally rare features scores
+/// higher) and a favored/non-favored multiplier. Factored out so both the
+/// linear scorer and the incremental scheduler's `cached_score` maintenance
+/// compute it identically.
+fn compute_score(entry: &CorpusEntry, global_edge_hits: &HashMap<u64, u64>) -> f64 {
+    let size_factor = 1.0 / (1.0 + entry.logical_js_len as f64 * 0.001);
+    let success_factor = 1.0 + entry.success_count as f64 * 0.2;
+    let coverage_factor = 1.0 + entry.coverage_found as f64 * 0.1;
+    let error_penalty = 1.0 / (1.0 + entry.error_count as f64 * 0.3);
+    let timeout_penalty = 1.0 / (1.0 + entry.timeout_count as f64 * 0.4);
+    let usage_penalty = 1.0 / (1.0 + entry.times_used as f64 * 0.2);
+    let rarity_factor = 1.0 + rarity(entry, global_edge_hits);
+    let favored_multiplier = if entry.is_favored { 2.0 } else { 0.5 };
+    entry.performance_score
+        * size_factor
+        * success_factor
+        * coverage_factor
+        * error_penalty
+        * timeout_penalty
+        * usage_penalty
+        * rarity_factor
+        * favored_multiplier
+}
+
+/// Which of the two `select_next_input` scheduling strategies is active.
+/// `Linear` is the original O(n)-per-call rescore-and-walk; `Incremental`
+/// is the default priority-queue-style scheduler. Exposed so the old path
+/// stays available for A/B comparison rather than being deleted outright.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SchedulerMode {
+    Linear,
+    #[default]
+    Incremental,
+}
+
+/// Trades RAM for CPU the way `git pack-objects --window-memory` style
+/// less-time/less-memory knobs do: `LessTime` (default) keeps every entry's
+/// `js_code`/`program_ir` inline so nothing needs decompressing before use;
+/// `LessMemory` stores only a deflate blob per entry in `CorpusManager`,
+/// decompressing lazily the moment an entry is actually selected.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum StorageMode {
+    #[default]
+    LessTime,
+    LessMemory,
+}
+
+/// The `LessMemory`-mode compressed form of one entry's `js_code`/`program_ir`,
+/// keyed by entry index in `CorpusManager::compressed_blobs`.
+#[derive(Clone)]
+struct CompressedBlob {
+    js_blob: Vec<u8>,
+    ir_blob: Vec<u8>,
+}
+
+fn compress_text(data: &str) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data.as_bytes()).expect("in-memory zlib encode cannot fail");
+    encoder.finish().expect("in-memory zlib encode cannot fail")
+}
+
+fn decompress_text(blob: &[u8]) -> String {
+    let mut decoder = ZlibDecoder::new(blob);
+    let mut out = String::new();
+    decoder.read_to_string(&mut out).expect("corpus blob was compressed by compress_text and cannot fail to decode");
+    out
+}
+
+/// Which digest `flush_to_disk`/`load_from_disk` use for `CorpusEntry::checksum`:
+/// `Crc32c` (default) is cheap enough to run on every flush, `Sha256` trades
+/// speed for collision resistance when a campaign's reproducers matter more
+/// than throughput.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ChecksumAlgorithm {
+    #[default]
+    Crc32c,
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    fn as_str(self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Crc32c => "crc32c",
+            ChecksumAlgorithm::Sha256 => "sha256",
+        }
+    }
+
+    fn from_str(s: &str) -> Self {
+        match s {
+            "sha256" => ChecksumAlgorithm::Sha256,
+            _ => ChecksumAlgorithm::Crc32c,
+        }
+    }
+}
+
+const CHECKSUM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Computes `data`'s digest under `algorithm`, feeding the hasher
+/// `CHECKSUM_CHUNK_SIZE` chunks at a time, and returns a lowercase hex
+/// string. It reads `data` in its own pass: `flush_to_disk` writes the
+/// bytes first and checksums them afterwards.
+fn compute_checksum(algorithm: ChecksumAlgorithm, data: &[u8]) -> String {
+    match algorithm {
+        ChecksumAlgorithm::Crc32c => {
+            let mut crc: u32 = 0;
+            for chunk in data.chunks(CHECKSUM_CHUNK_SIZE) {
+                crc = crc32c::crc32c_append(crc, chunk);
+            }
+            format!("{:08x}", crc)
+        }
+        ChecksumAlgorithm::Sha256 => {
+            let mut hasher = sha2::Sha256::new();
+            for chunk in data.chunks(CHECKSUM_CHUNK_SIZE) {
+                sha2::Digest::update(&mut hasher, chunk);
+            }
+            let digest = sha2::Digest::finalize(hasher);
+            digest.iter().map(|b| format!("{:02x}", b)).collect()
+        }
+    }
+}
+
+const ENCRYPT_CHUNK_SIZE: usize = 64 * 1024;
+const GCM_NONCE_SIZE: usize = 12;
+const GCM_TAG_SIZE: usize = 16;
 
+/// Encrypts `plaintext` for on-disk storage, chunk by chunk, so a partial
+/// read only needs to decrypt (and authenticate) the chunks it actually
+/// touches: each `ENCRYPT_CHUNK_SIZE` plaintext chunk gets its own fresh
+/// random 96-bit nonce and its own GCM authentication tag, laid out as
+/// `nonce || ciphertext+tag` per chunk, concatenated.
+fn encrypt_corpus_blob(key: &[u8; 32], plaintext: &[u8]) -> Vec<u8> {
+    let cipher = Aes256Gcm::new(key.into());
+    let mut out = Vec::with_capacity(plaintext.len() + (plaintext.len() / ENCRYPT_CHUNK_SIZE + 1) * (GCM_NONCE_SIZE + GCM_TAG_SIZE));
+    for chunk in plaintext.chunks(ENCRYPT_CHUNK_SIZE) {
+        let mut nonce_bytes = [0u8; GCM_NONCE_SIZE];
+        rand::thread_rng().fill(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+        let ciphertext = cipher
+            .encrypt(nonce, chunk)
+            .expect("AES-256-GCM encryption of an in-memory corpus chunk cannot fail");
+        out.extend_from_slice(&nonce_bytes);
+        out.extend_from_slice(&ciphertext);
+    }
+    out
+}
+
+/// Reverses `encrypt_corpus_blob`, authenticating each chunk independently
+/// so a truncated or corrupted chunk fails without needing to process the
+/// whole blob first.
+fn decrypt_corpus_blob(key: &[u8; 32], blob: &[u8]) -> io::Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new(key.into());
+    let full_record_len = GCM_NONCE_SIZE + ENCRYPT_CHUNK_SIZE + GCM_TAG_SIZE;
+    let mut out = Vec::with_capacity(blob.len());
+    let mut offset = 0;
+    while offset < blob.len() {
+        let remaining = blob.len() - offset;
+        let record_len = remaining.min(full_record_len);
+        if record_len < GCM_NONCE_SIZE + GCM_TAG_SIZE {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "truncated corpus ciphertext chunk"));
+        }
+        let record = &blob[offset..offset + record_len];
+        let (nonce_bytes, ciphertext) = record.split_at(GCM_NONCE_SIZE);
+        let nonce = Nonce::from_slice(nonce_bytes);
+        let plaintext = cipher
+            .decrypt(nonce, ciphertext)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "corpus chunk failed AES-GCM authentication"))?;
+        out.extend_from_slice(&plaintext);
+        offset += record_len;
+    }
+    Ok(out)
+}
+
+/// Derives a 256-bit data-encryption key from a passphrase via Argon2
+/// (memory-hard, so offline brute-forcing a weak passphrase is expensive),
+/// using the caller-supplied `salt`. The derived key is held only in memory
+/// by `CorpusManager` and is never written alongside the ciphertext.
+fn derive_key_from_passphrase(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .expect("argon2 key derivation failed");
+    key
+}
+
+/// Vose's alias method: O(1) sampling from a discrete weighted distribution
+/// after an O(n) build. `CorpusManager` builds one of these lazily over
+/// `cached_score` and only rebuilds it once per-entry score drift (or a
+/// structural change via `rebuild_favored`) invalidates it, rather than
+/// rescoring the whole corpus on every `select_next_input` call.
+#[derive(Clone)]
+struct AliasTable {
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+    entry_indices: Vec<u32>,
+}
+
+impl AliasTable {
+    fn build(weighted: &[(u32, f64)]) -> Option<Self> {
+        let n = weighted.len();
+        if n == 0 {
+            return None;
+        }
+        let total: f64 = weighted.iter().map(|&(_, w)| w).sum();
+        if !(total > 0.0) {
+            return None;
+        }
+
+        let mut scaled: Vec<f64> = weighted.iter().map(|&(_, w)| w / total * n as f64).collect();
+        let mut prob = vec![0.0; n];
+        let mut alias = vec![0usize; n];
+        let mut small: Vec<usize> = Vec::new();
+        let mut large: Vec<usize> = Vec::new();
+        for (i, &s) in scaled.iter().enumerate() {
+            if s < 1.0 {
+                small.push(i);
+            } else {
+                large.push(i);
+            }
+        }
+        while let (Some(s), Some(l)) = (small.pop(), large.pop()) {
+            prob[s] = scaled[s];
+            alias[s] = l;
+            scaled[l] = (scaled[l] + scaled[s]) - 1.0;
+            if scaled[l] < 1.0 {
+                small.push(l);
+            } else {
+                large.push(l);
+            }
+        }
+        for i in large.into_iter().chain(small) {
+            prob[i] = 1.0;
+        }
+
+        Some(AliasTable { prob, alias, entry_indices: weighted.iter().map(|&(idx, _)| idx).collect() })
+    }
+
+    fn sample(&self, rng: &mut impl Rng) -> u32 {
+        let i = rng.gen_range(0..self.entry_indices.len());
+        if rng.gen::<f64>() < self.prob[i] {
+            self.entry_indices[i]
+        } else {
+            self.entry_indices[self.alias[i]]
+        }
+    }
 }
 #[derive(Clone)]
 pub struct CorpusManager {
@@ -37,19 +362,50 @@ pub struct CorpusManager {
     max_size: usize,
     min_energy: f64,
     total_coverage: HashMap<u64, u64>,
+    global_edge_hits: HashMap<u64, u64>, // edge id -> number of `record_entry_coverage` calls that have reported that edge, the real per-edge rarity data `rarity()` scores against
     corpus_hash: HashMap<u64, bool>,
     last_new_coverage: Instant,
     stats: CorpusStats,
     selection_counter: usize, // Track how many times select_next_input is called
     pub bytecode_collector: Option<BytecodeCollector>, // Add bytecode collector
+    lineage_path: Option<std::path::PathBuf>, // Where to write the --dump-lineage DOT graph, if enabled
+    ir_hash_index: HashMap<u64, u32>, // program_ir content hash -> entry index, for lineage lookups across processes
+    favored_edges: HashMap<u64, u32>, // edge id -> index of the cheapest known entry covering it (IndexesLenTimeMinimizerScheduler)
+    favored: HashSet<u32>, // union of favored_edges' values; select_next_input is biased toward this set
+    duplicates_suppressed: u64, // count of inserts rejected by corpus_hash or as subsumed by add_entry_if_not_subsumed, for dedup_stats()
+    persist_dir: Option<PathBuf>, // worker-specific on-disk corpus directory, set via set_persist_dir()
+    scheduler_mode: SchedulerMode, // select_next_input strategy; see SchedulerMode
+    total_energy: f64, // sum of cached_score across all entries, maintained incrementally
+    total_favored_energy: f64, // sum of cached_score across favored entries, maintained incrementally
+    alias_all: Option<AliasTable>, // lazily-built sampler over all entries
+    alias_all_energy: f64, // total_energy snapshot when alias_all was built, for drift invalidation
+    alias_favored: Option<AliasTable>, // lazily-built sampler over favored entries
+    alias_favored_energy: f64, // total_favored_energy snapshot when alias_favored was built
+    avg_rarity: f64, // mean rarity() across all entries, refreshed by rebuild_favored(), for stats
+    storage_mode: StorageMode, // LessTime (default, inline strings) or LessMemory (compressed blobs)
+    compressed_blobs: HashMap<u32, CompressedBlob>, // entry index -> compressed js_code/program_ir, only populated in LessMemory mode
+    bucket_store: Option<Arc<Mutex<BucketStore>>>, // optional mmap-backed bucket-sharded store mirrored on writes; Arc<Mutex<_>> keeps CorpusManager Clone despite BucketStore's File/MmapMut handles
+    content_refcounts: HashMap<u64, u32>, // hash_str(js_code) -> number of entries sharing that content
+    content_owner: HashMap<u64, u32>, // hash_str(js_code) -> index of the entry currently holding the real bytes (the others are cleared)
+    entry_content_hash: HashMap<u32, u64>, // entry index -> its content hash, so removal/materialization don't need to rehash js_code
+    checksum_algorithm: ChecksumAlgorithm, // digest flush_to_disk computes and load_from_disk verifies
+    corrupted_entries_dropped: u64, // count of load_from_disk entries quarantined for a checksum mismatch
+    encryption_key: Option<[u8; 32]>, // AES-256-GCM data-encryption key for at-rest js_code; None means flush_to_disk/load_from_disk store plaintext
+    mem_high_water_bytes: Option<u64>, // resident-bytes threshold above which should_keep_entry gets stricter and prune_for_memory_pressure kicks in
+    mem_low_water_bytes: Option<u64>, // prune_for_memory_pressure's target: evict lowest-scoring entries until estimated resident usage drops to this
+    entries_pruned_for_memory: u64, // count of entries evicted by prune_for_memory_pressure, for stats
 }
 
 impl CorpusEntry {
     pub fn new(program_ir: String, js_code: String) -> Self {
+        let logical_ir_len = program_ir.len();
+        let logical_js_len = js_code.len();
         Self {
             index: 0 as u32,
             program_ir: program_ir,
             js_code: js_code,
+            logical_js_len,
+            logical_ir_len,
             times_used: 0,
             coverage_found: 0,
             success_count: 0,
@@ -65,8 +421,14 @@ impl CorpusEntry {
             module_features: HashMap::new(),
             bytecode_analysis: None,
             has_novel_bytecode: false,
+            parent_index: None,
+            discovered_by_pass: None,
+            exec_time_micros: 1,
+            is_favored: false,
+            cached_score: 1.0,
+            checksum: String::new(),
         }
-        
+
     }
     
   
@@ -91,6 +453,10 @@ impl CorpusManager {
      const MIN_TRIES_BEFORE_ADVANCE: u32 = 10;  // Minimum attempts before considering stage advance
      const SUCCESS_RATE_THRESHOLD: f64 = 0.1;   // If success rate below this, advance stage
      const COVERAGE_STALENESS_THRESHOLD: Duration = Duration::from_secs(300); // 5 minutes
+     // Probability that select_next_input draws from the favored (minimal
+     // edge-covering) set rather than the whole corpus; mirrors LibAFL's
+     // IndexesLenTimeMinimizerScheduler default of mostly-favored selection.
+     const FAVORED_SELECTION_PROBABILITY: f64 = 0.95;
  
     pub fn new(worker_id: usize, max_size: usize) -> Self {
         Self {
@@ -99,6 +465,7 @@ impl CorpusManager {
             max_size,
             min_energy: 0.1,
             total_coverage: HashMap::new(),
+            global_edge_hits: HashMap::new(),
             corpus_hash: HashMap::new(),
             last_new_coverage: Instant::now(),
             stats: CorpusStats {
@@ -109,8 +476,533 @@ impl CorpusManager {
             },
             selection_counter: 0,
             bytecode_collector: None,
+            lineage_path: None,
+            ir_hash_index: HashMap::new(),
+            favored_edges: HashMap::new(),
+            favored: HashSet::new(),
+            duplicates_suppressed: 0,
+            persist_dir: None,
+            scheduler_mode: SchedulerMode::default(),
+            total_energy: 0.0,
+            total_favored_energy: 0.0,
+            alias_all: None,
+            alias_all_energy: 0.0,
+            alias_favored: None,
+            alias_favored_energy: 0.0,
+            avg_rarity: 0.0,
+            storage_mode: StorageMode::default(),
+            compressed_blobs: HashMap::new(),
+            bucket_store: None,
+            content_refcounts: HashMap::new(),
+            content_owner: HashMap::new(),
+            entry_content_hash: HashMap::new(),
+            checksum_algorithm: ChecksumAlgorithm::default(),
+            corrupted_entries_dropped: 0,
+            encryption_key: None,
+            mem_high_water_bytes: None,
+            mem_low_water_bytes: None,
+            entries_pruned_for_memory: 0,
+        }
+    }
+
+    /// Enables memory-pressure-driven retention: once `mem_telemetry::sample`
+    /// reports resident bytes above `high_water_bytes`, `should_keep_entry`
+    /// drops the bytecode-novelty fallback (only genuinely new coverage keeps
+    /// an entry), and `prune_for_memory_pressure` evicts the lowest-scoring
+    /// entries until estimated resident usage is back down to
+    /// `low_water_bytes`. Disabled (unlimited corpus growth) by default.
+    pub fn set_memory_pressure_thresholds(&mut self, high_water_bytes: u64, low_water_bytes: u64) {
+        self.mem_high_water_bytes = Some(high_water_bytes);
+        self.mem_low_water_bytes = Some(low_water_bytes);
+    }
+
+    /// Count of entries evicted by `prune_for_memory_pressure`, for
+    /// `dump_stats_to_json`/`print_stats`.
+    pub fn entries_pruned_for_memory(&self) -> u64 {
+        self.entries_pruned_for_memory
+    }
+
+    /// True once jemalloc reports resident memory above the configured high
+    /// water mark; used by `should_keep_entry` to drop the bytecode-novelty
+    /// fallback, and by callers deciding whether to run
+    /// `prune_for_memory_pressure` this cycle. Fails safe (not under
+    /// pressure) if thresholds aren't configured or the sample errors.
+    fn under_memory_pressure(&self) -> bool {
+        let Some(high_water) = self.mem_high_water_bytes else {
+            return false;
+        };
+        match crate::mem_telemetry::sample() {
+            Ok(sample) => sample.resident_bytes > high_water,
+            Err(_) => false,
+        }
+    }
+
+    /// Enables at-rest encryption of `js_code` (via `flush_to_disk`/
+    /// `load_from_disk`) using a caller-supplied 256-bit key. Call before
+    /// the first `set_persist_dir`/`flush_to_disk`.
+    pub fn set_encryption_key(&mut self, key: [u8; 32]) {
+        self.encryption_key = Some(key);
+    }
+
+    /// Same as `set_encryption_key`, but derives the key from a passphrase
+    /// via Argon2 instead of requiring the caller to manage raw key bytes.
+    /// `salt` must be supplied and kept alongside the corpus (not secret,
+    /// but must be stable across runs to re-derive the same key).
+    pub fn set_encryption_passphrase(&mut self, passphrase: &str, salt: &[u8]) {
+        self.encryption_key = Some(derive_key_from_passphrase(passphrase, salt));
+    }
+
+    /// Selects the digest `flush_to_disk`/`load_from_disk` use for
+    /// `CorpusEntry::checksum`. Call before the first `set_persist_dir`, since
+    /// entries already flushed under a different algorithm keep their old
+    /// checksum until next written.
+    pub fn set_checksum_algorithm(&mut self, algorithm: ChecksumAlgorithm) {
+        self.checksum_algorithm = algorithm;
+    }
+
+    /// How many persisted entries `load_from_disk` quarantined (refused to
+    /// add to the corpus) because their recomputed digest didn't match the
+    /// checksum recorded at flush time, for the admin/metrics endpoints.
+    pub fn corrupted_entries_dropped(&self) -> u64 {
+        self.corrupted_entries_dropped
+    }
+
+    /// Opens (or creates) a memory-mapped, bucket-sharded store under
+    /// `root/worker_<id>/buckets` and mirrors every subsequent `add_entry`/
+    /// `update_entry_error`/`update_entry_timeout` write into it. Once a
+    /// bucket store is configured, `add_entry` drops the in-memory
+    /// `js_code` for every new entry right after mirroring it, and
+    /// `materialize` pages it back in from the store on selection -- so a
+    /// worker's resident corpus scales with scalar metadata rather than
+    /// with every entry's full `js_code`, letting it hold a corpus far
+    /// larger than would fit in RAM under `StorageMode::LessTime`.
+    pub fn set_bucket_store_dir(&mut self, root: PathBuf, bucket_bits: u32) -> io::Result<()> {
+        let worker_dir = root.join(format!("worker_{}", self.worker_id)).join("buckets");
+        let store = BucketStore::open(&worker_dir, bucket_bits)?;
+        self.bucket_store = Some(Arc::new(Mutex::new(store)));
+        Ok(())
+    }
+
+    fn mirror_to_bucket_store(&self, entry: &CorpusEntry) {
+        let Some(store) = &self.bucket_store else {
+            return;
+        };
+        let meta = EntryMeta {
+            times_used: entry.times_used,
+            success_count: entry.success_count,
+            coverage_found: entry.coverage_found,
+            error_count: entry.error_count,
+            timeout_count: entry.timeout_count,
+            performance_score: entry.performance_score,
+        };
+        if let Err(e) = store.lock().unwrap().put(entry.index, meta, entry.js_code.as_bytes()) {
+            eprintln!("Failed to mirror entry {} to bucket store: {}", entry.index, e);
+        }
+    }
+
+    fn mirror_meta_update(&self, entry: &CorpusEntry) {
+        let Some(store) = &self.bucket_store else {
+            return;
+        };
+        let meta = EntryMeta {
+            times_used: entry.times_used,
+            success_count: entry.success_count,
+            coverage_found: entry.coverage_found,
+            error_count: entry.error_count,
+            timeout_count: entry.timeout_count,
+            performance_score: entry.performance_score,
+        };
+        store.lock().unwrap().update_meta(entry.index, meta);
+    }
+
+    /// Reads an entry's scalar metadata and `js_code` back out of the
+    /// bucket store (bypassing `entries` entirely), for callers that want
+    /// to scale a corpus beyond what fits in RAM. Returns `None` if no
+    /// bucket store is configured or the index isn't present in it.
+    pub fn bucket_store_get(&self, index: u32) -> Option<(EntryMeta, Vec<u8>)> {
+        let store = self.bucket_store.as_ref()?;
+        store.lock().unwrap().get(index).ok().flatten()
+    }
+
+    /// Selects `LessTime` (default) or `LessMemory` storage. Call right
+    /// after construction, before any entries are added -- switching modes
+    /// on a corpus that already holds entries only affects entries added
+    /// afterward.
+    pub fn set_storage_mode(&mut self, mode: StorageMode) {
+        self.storage_mode = mode;
+    }
+
+    /// `(logical_bytes, resident_bytes)` across every entry's `js_code` +
+    /// `program_ir`: logical is what plain `LessTime` mode with no bucket
+    /// store would hold in memory; resident is what's actually resident
+    /// right now, computed directly off the (possibly cleared) fields plus
+    /// any `compressed_blobs` standing in for them, rather than switching on
+    /// `storage_mode` alone -- so it also reflects the savings from a
+    /// configured bucket store, which clears `js_code` independently of
+    /// `StorageMode`. For `dump_stats_to_json`/`print_stats`.
+    pub fn storage_byte_totals(&self) -> (u64, u64) {
+        let logical: u64 = self.entries.iter().map(|e| (e.logical_js_len + e.logical_ir_len) as u64).sum();
+        let resident: u64 = self.entries.iter().map(|e| (e.js_code.len() + e.program_ir.len()) as u64).sum::<u64>()
+            + self
+                .compressed_blobs
+                .values()
+                .map(|blob| (blob.js_blob.len() + blob.ir_blob.len()) as u64)
+                .sum::<u64>();
+        (logical, resident)
+    }
+
+    /// Restores `entry.js_code`/`entry.program_ir` so callers that clone an
+    /// entry out of the corpus (select_next_input, get_random_program_ir)
+    /// get the real content rather than the placeholder empty strings left
+    /// by whichever backing cleared them. Tries the bucket store first
+    /// (it's keyed directly by `entry.index`, so it's correct regardless of
+    /// dedup ownership), then falls back to `compressed_blobs` in
+    /// `LessMemory` mode. A no-op when neither backing is configured, where
+    /// the fields are never cleared in the first place. Also restores
+    /// content-deduplicated `js_code` (see `dedup_content`) for entries that
+    /// aren't the canonical owner of their content and weren't resolved by
+    /// either backing above.
+    fn materialize(&self, entry: &mut CorpusEntry) {
+        if let Some((_, js_bytes)) = self.bucket_store_get(entry.index) {
+            if let Ok(js_code) = String::from_utf8(js_bytes) {
+                entry.js_code = js_code;
+            }
+        } else if self.storage_mode == StorageMode::LessMemory {
+            if let Some(blob) = self.compressed_blobs.get(&entry.index) {
+                entry.js_code = decompress_text(&blob.js_blob);
+                entry.program_ir = decompress_text(&blob.ir_blob);
+            }
+        }
+        if entry.js_code.is_empty() {
+            if let Some(content_key) = self.entry_content_hash.get(&entry.index) {
+                if let Some(&owner_index) = self.content_owner.get(content_key) {
+                    if owner_index != entry.index {
+                        if let Some(owner) = self.entries.iter().find(|e| e.index == owner_index) {
+                            entry.js_code = owner.js_code.clone();
+                        }
+                        if entry.js_code.is_empty() {
+                            if let Some(blob) = self.compressed_blobs.get(&owner_index) {
+                                entry.js_code = decompress_text(&blob.js_blob);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Content-addressed dedup over `js_code` (distinct from the IR-level
+    /// `corpus_hash` rejection in `add_entry`, which never lets a duplicate
+    /// become an entry at all): when `entry`'s code text matches content
+    /// already held by another entry, bump that content's refcount and
+    /// clear `entry.js_code` rather than storing the same bytes twice --
+    /// `materialize` restores it from the canonical owner on selection.
+    /// The first entry to ever hold a given content stays its canonical
+    /// owner and keeps its `js_code` populated.
+    fn dedup_content(&mut self, entry: &mut CorpusEntry) {
+        let content_key = hash_str(&entry.js_code);
+        self.entry_content_hash.insert(entry.index, content_key);
+        let refcount = self.content_refcounts.entry(content_key).or_insert(0);
+        *refcount += 1;
+        if *refcount > 1 {
+            entry.js_code = String::new();
+        } else {
+            self.content_owner.insert(content_key, entry.index);
         }
     }
+
+    /// Decrements the refcount for `index`'s content and, if it was the
+    /// canonical owner of content still referenced by other entries,
+    /// promotes one of them to hold the real bytes before this entry is
+    /// dropped. Call before removing an entry from `self.entries`, as
+    /// `prune_for_memory_pressure` does for each entry it evicts.
+    pub fn release_content(&mut self, index: u32) {
+        let Some(content_key) = self.entry_content_hash.remove(&index) else {
+            return;
+        };
+        let Some(refcount) = self.content_refcounts.get_mut(&content_key) else {
+            return;
+        };
+        *refcount = refcount.saturating_sub(1);
+        if *refcount == 0 {
+            self.content_refcounts.remove(&content_key);
+            self.content_owner.remove(&content_key);
+            return;
+        }
+
+        let is_owner = self.content_owner.get(&content_key) == Some(&index);
+        if !is_owner {
+            return;
+        }
+        let Some(owner) = self.entries.iter().find(|e| e.index == index) else {
+            return;
+        };
+        let js_code = owner.js_code.clone();
+        let Some(new_owner_index) = self
+            .entry_content_hash
+            .iter()
+            .find(|&(&idx, &key)| idx != index && key == content_key)
+            .map(|(&idx, _)| idx)
+        else {
+            return;
+        };
+        if let Some(new_owner) = self.entries.iter_mut().find(|e| e.index == new_owner_index) {
+            new_owner.js_code = js_code;
+        }
+        self.content_owner.insert(content_key, new_owner_index);
+    }
+
+    /// `(unique_content_count, total_entries)` for the admin/metrics
+    /// endpoints: the gap between them is how many entries are sharing
+    /// another entry's `js_code` bytes rather than storing their own copy.
+    pub fn content_dedup_stats(&self) -> (usize, usize) {
+        (self.content_refcounts.len(), self.entries.len())
+    }
+
+    /// Number of entries currently in the favored (minimal edge-covering)
+    /// set, for the admin/metrics endpoints.
+    pub fn favored_set_size(&self) -> usize {
+        self.favored.len()
+    }
+
+    /// Mean `rarity()` across all entries as of the last `rebuild_favored`
+    /// pass, for the admin/metrics endpoints.
+    pub fn avg_rarity(&self) -> f64 {
+        self.avg_rarity
+    }
+
+    /// How many inserts were rejected, for the admin/metrics endpoints:
+    /// `add_entry` calls that matched an already-present `program_ir` hash,
+    /// plus `add_entry_if_not_subsumed` calls whose coverage was a strict
+    /// subset of an existing entry's.
+    pub fn dedup_stats(&self) -> u64 {
+        self.duplicates_suppressed
+    }
+
+    /// Switches between the incremental (default) and linear `select_next_input`
+    /// scheduling strategies, so the old behavior stays available for
+    /// side-by-side comparison instead of being deleted.
+    pub fn set_scheduler_mode(&mut self, mode: SchedulerMode) {
+        self.scheduler_mode = mode;
+    }
+
+    /// Recomputes a single entry's `cached_score` and folds the delta into
+    /// the running `total_energy`/`total_favored_energy` totals in O(1),
+    /// instead of rescoring the whole corpus. Invalidates whichever alias
+    /// table(s) have drifted past `ENERGY_DRIFT_THRESHOLD` since their last
+    /// build, so they get rebuilt lazily on the next selection.
+    fn refresh_entry_score(&mut self, index: u32) {
+        const ENERGY_DRIFT_THRESHOLD: f64 = 0.2;
+
+        let global_edge_hits = &self.global_edge_hits;
+        let Some(entry) = self.entries.iter_mut().find(|e| e.index == index) else {
+            return;
+        };
+        let old_score = entry.cached_score;
+        let new_score = compute_score(entry, global_edge_hits);
+        entry.cached_score = new_score;
+        let is_favored = entry.is_favored;
+        let delta = new_score - old_score;
+
+        self.total_energy += delta;
+        if is_favored {
+            self.total_favored_energy += delta;
+        }
+
+        if self.alias_all_energy > 0.0
+            && ((self.total_energy - self.alias_all_energy).abs() / self.alias_all_energy) > ENERGY_DRIFT_THRESHOLD
+        {
+            self.alias_all = None;
+        }
+        if self.alias_favored_energy > 0.0
+            && ((self.total_favored_energy - self.alias_favored_energy).abs() / self.alias_favored_energy)
+                > ENERGY_DRIFT_THRESHOLD
+        {
+            self.alias_favored = None;
+        }
+    }
+
+    /// Points this corpus at a worker-specific on-disk directory (created if
+    /// missing, one subdirectory per worker_id so workers never clobber each
+    /// other's files, mirroring honggfuzz's workspace-per-run layout) and
+    /// immediately repopulates `entries`/`total_coverage`/`corpus_hash` from
+    /// whatever was flushed there by a previous run. Call once at startup,
+    /// before the first `should_reseed()` check.
+    pub fn set_persist_dir(&mut self, root: PathBuf) -> io::Result<()> {
+        let worker_dir = root.join(format!("worker_{}", self.worker_id));
+        std::fs::create_dir_all(&worker_dir)?;
+        self.persist_dir = Some(worker_dir);
+        self.load_from_disk()
+    }
+
+    /// Appends every entry not already on disk to `persist_dir` as three
+    /// files named by its content hash: `<hash>.ir` (`program_ir`),
+    /// `<hash>.js` (`js_code`, AES-256-GCM-encrypted chunk-by-chunk if an
+    /// encryption key is set via `set_encryption_key`/`set_encryption_passphrase`),
+    /// and `<hash>.meta.json` (a small sidecar with
+    /// `coverage_found`/`success_count`/`feature_frequency`). Existing files
+    /// are left untouched, so this is safe to call repeatedly and safe to
+    /// resume from if the process is killed mid-flush.
+    ///
+    /// Entries are materialized before writing: `dedup_content` clears
+    /// `entry.js_code` in place for every non-canonical owner of a piece of
+    /// content (and `LessMemory`/a configured bucket store can clear it too),
+    /// so writing `entry.js_code` directly here would silently persist an
+    /// empty `.js` file for any entry that isn't the one holding the real
+    /// bytes in RAM right now.
+    pub fn flush_to_disk(&self) -> io::Result<()> {
+        let Some(ref dir) = self.persist_dir else {
+            return Ok(());
+        };
+        for raw_entry in &self.entries {
+            let mut entry = raw_entry.clone();
+            self.materialize(&mut entry);
+            let hash = dedup_hash(&entry.program_ir);
+            let ir_file = dir.join(format!("{:016x}.ir", hash));
+            if ir_file.exists() {
+                continue;
+            }
+            std::fs::write(&ir_file, &entry.program_ir)?;
+            let js_bytes = match &self.encryption_key {
+                Some(key) => encrypt_corpus_blob(key, entry.js_code.as_bytes()),
+                None => entry.js_code.as_bytes().to_vec(),
+            };
+            std::fs::write(dir.join(format!("{:016x}.js", hash)), &js_bytes)?;
+            let checksum = compute_checksum(self.checksum_algorithm, &js_bytes);
+            let sidecar = serde_json::json!({
+                "coverage_found": entry.coverage_found,
+                "success_count": entry.success_count,
+                "feature_frequency": entry.feature_frequency,
+                "checksum": checksum,
+                "checksum_algorithm": self.checksum_algorithm.as_str(),
+                "encrypted": self.encryption_key.is_some(),
+            });
+            std::fs::write(
+                dir.join(format!("{:016x}.meta.json", hash)),
+                serde_json::to_string_pretty(&sidecar).unwrap(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Repopulates `entries` from whatever `<hash>.ir`/`<hash>.js`/
+    /// `<hash>.meta.json` triples are present in `persist_dir`, restoring
+    /// each entry's coverage stats from its sidecar so a resumed worker
+    /// doesn't cold-start with `should_reseed()` returning true.
+    fn load_from_disk(&mut self) -> io::Result<()> {
+        let Some(ref dir) = self.persist_dir else {
+            return Ok(());
+        };
+        let mut loaded = 0;
+        for file in std::fs::read_dir(dir)? {
+            let file = file?;
+            let path = file.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("ir") {
+                continue;
+            }
+            let js_path = path.with_extension("js");
+            let meta_path = path.with_extension("meta.json");
+            if !js_path.exists() {
+                continue;
+            }
+            let program_ir = std::fs::read_to_string(&path)?;
+            let js_bytes_on_disk = std::fs::read(&js_path)?;
+            let mut feature_frequency = HashMap::new();
+            let mut stored_checksum: Option<String> = None;
+            let mut stored_algorithm = self.checksum_algorithm;
+            let mut coverage_found = 0;
+            let mut success_count = 0;
+            let mut encrypted = false;
+            if let Ok(raw) = std::fs::read_to_string(&meta_path) {
+                if let Ok(sidecar) = serde_json::from_str::<serde_json::Value>(&raw) {
+                    coverage_found = sidecar["coverage_found"].as_u64().unwrap_or(0) as u32;
+                    success_count = sidecar["success_count"].as_u64().unwrap_or(0) as u32;
+                    if let Some(map) = sidecar["feature_frequency"].as_object() {
+                        for (key, value) in map {
+                            if let Ok(edge) = key.parse::<u64>() {
+                                feature_frequency.insert(edge, value.as_u64().unwrap_or(0));
+                            }
+                        }
+                    }
+                    if let Some(checksum) = sidecar["checksum"].as_str() {
+                        stored_checksum = Some(checksum.to_string());
+                    }
+                    if let Some(algorithm) = sidecar["checksum_algorithm"].as_str() {
+                        stored_algorithm = ChecksumAlgorithm::from_str(algorithm);
+                    }
+                    encrypted = sidecar["encrypted"].as_bool().unwrap_or(false);
+                }
+            }
+            if let Some(expected) = &stored_checksum {
+                let actual = compute_checksum(stored_algorithm, &js_bytes_on_disk);
+                if &actual != expected {
+                    eprintln!(
+                        "[CORPUS] Quarantining {}: checksum mismatch (expected {}, got {})",
+                        js_path.display(),
+                        expected,
+                        actual
+                    );
+                    self.corrupted_entries_dropped += 1;
+                    continue;
+                }
+            }
+            let js_plaintext = if encrypted {
+                let Some(key) = &self.encryption_key else {
+                    eprintln!(
+                        "[CORPUS] Skipping {}: entry is encrypted but no encryption key is configured",
+                        js_path.display()
+                    );
+                    continue;
+                };
+                match decrypt_corpus_blob(key, &js_bytes_on_disk) {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        eprintln!("[CORPUS] Quarantining {}: decryption failed: {}", js_path.display(), e);
+                        self.corrupted_entries_dropped += 1;
+                        continue;
+                    }
+                }
+            } else {
+                js_bytes_on_disk
+            };
+            let Ok(js_code) = String::from_utf8(js_plaintext) else {
+                eprintln!("[CORPUS] Quarantining {}: decrypted bytes are not valid UTF-8", js_path.display());
+                self.corrupted_entries_dropped += 1;
+                continue;
+            };
+            let mut entry = CorpusEntry::new(program_ir, js_code);
+            entry.checksum = stored_checksum.unwrap_or_default();
+            entry.coverage_found = coverage_found;
+            entry.success_count = success_count;
+            if self.add_entry(entry) {
+                let index = (self.entries.len() - 1) as u32;
+                if let Some(added) = self.entries.iter_mut().find(|e| e.index == index) {
+                    added.coverage_found = coverage_found;
+                    added.success_count = success_count;
+                    added.feature_frequency = feature_frequency;
+                }
+                self.total_coverage.insert(index as u64, coverage_found as u64);
+                loaded += 1;
+            }
+        }
+        if loaded > 0 {
+            self.rebuild_favored();
+            println!("[CORPUS] Loaded {} entries from persisted corpus at {}", loaded, dir.display());
+        }
+        Ok(())
+    }
+
+    /// Looks up the local index of the entry whose `program_ir` hashes to
+    /// `ir_hash`, e.g. to resolve a parent entry referenced by a remote worker.
+    pub fn find_index_by_ir_hash(&self, ir_hash: u64) -> Option<u32> {
+        self.ir_hash_index.get(&ir_hash).copied()
+    }
+
+    /// Enables periodic lineage export; the DOT graph is rewritten every time
+    /// `add_entry_with_lineage` adds an entry, whether or not it has a parent.
+    pub fn set_lineage_path(&mut self, path: std::path::PathBuf) {
+        self.lineage_path = Some(path);
+    }
   
     pub fn update_feature_frequency(&mut self, index: u32, features: &[u64]) {
         if let Some(entry) = self.entries.iter_mut().find(|e| e.index == index) {
@@ -118,12 +1010,90 @@ impl CorpusManager {
                 *entry.feature_frequency.entry(feature).or_insert(0) += 1;
             }
         }
+        self.rebuild_favored();
+    }
+
+    /// Records which edges an entry covers and how long it took to execute,
+    /// then recomputes the favored set. This is how the minimizer scheduler
+    /// below learns each entry's size/time cost without the executor having
+    /// to know about scheduling at all.
+    pub fn record_entry_coverage(&mut self, index: u32, edges: &[u64], exec_time_micros: u64) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.index == index) {
+            entry.exec_time_micros = exec_time_micros.max(1);
+            for &edge in edges {
+                entry.feature_frequency.entry(edge).or_insert(0);
+                *self.global_edge_hits.entry(edge).or_insert(0) += 1;
+            }
+        }
+        self.rebuild_favored();
+    }
+
+    /// `len(js_code) * exec_time_micros` — the score LibAFL's
+    /// IndexesLenTimeMinimizerScheduler ranks candidate edge-owners by;
+    /// lower is better (smaller and faster).
+    fn minimizer_score(entry: &CorpusEntry) -> f64 {
+        entry.logical_js_len as f64 * entry.exec_time_micros.max(1) as f64
+    }
+
+    /// Recomputes the favored set: for every coverage edge, the single
+    /// corpus entry with the smallest `minimizer_score` among those that
+    /// cover it becomes that edge's owner. The union of all edge owners is
+    /// the favored set `select_next_input` is biased toward, which keeps
+    /// the actively-mutated working set close to a minimal edge cover
+    /// instead of growing unbounded with redundant entries.
+    fn rebuild_favored(&mut self) {
+        let mut best_owner: HashMap<u64, (u32, f64)> = HashMap::new();
+        for entry in &self.entries {
+            let score = Self::minimizer_score(entry);
+            for &edge in entry.feature_frequency.keys() {
+                match best_owner.get(&edge) {
+                    Some(&(_, best_score)) if best_score <= score => {}
+                    _ => {
+                        best_owner.insert(edge, (entry.index, score));
+                    }
+                }
+            }
+        }
+        self.favored = best_owner.values().map(|&(idx, _)| idx).collect();
+        self.favored_edges = best_owner.into_iter().map(|(edge, (idx, _))| (edge, idx)).collect();
+
+        let favored = &self.favored;
+        let global_edge_hits = &self.global_edge_hits;
+        let mut total_energy = 0.0;
+        let mut total_favored_energy = 0.0;
+        let mut rarity_sum = 0.0;
+        for entry in self.entries.iter_mut() {
+            entry.is_favored = favored.contains(&entry.index);
+            entry.cached_score = compute_score(entry, global_edge_hits);
+            rarity_sum += rarity(entry, global_edge_hits);
+            total_energy += entry.cached_score;
+            if entry.is_favored {
+                total_favored_energy += entry.cached_score;
+            }
+        }
+        self.total_energy = total_energy;
+        self.total_favored_energy = total_favored_energy;
+        self.avg_rarity = if self.entries.is_empty() { 0.0 } else { rarity_sum / self.entries.len() as f64 };
+        // The favored set (and possibly every entry's score) just changed
+        // wholesale, so any cached alias table is stale.
+        self.alias_all = None;
+        self.alias_favored = None;
     }
     
    
 
 
-    pub fn add_entry(&mut self, mut entry: CorpusEntry) {
+    /// Adds `entry` to the corpus, unless a normalized-`program_ir` hash
+    /// matching it is already present (see `dedup_hash`), in which case the
+    /// insert is rejected and `duplicates_suppressed` is incremented.
+    /// Returns whether the entry was actually added.
+    pub fn add_entry(&mut self, mut entry: CorpusEntry) -> bool {
+        let dedup_key = dedup_hash(&entry.program_ir);
+        if self.corpus_hash.contains_key(&dedup_key) {
+            self.duplicates_suppressed += 1;
+            return false;
+        }
+
         entry.index = self.entries.len() as u32;
         entry.times_used = 0;
         entry.success_count = 0;
@@ -138,12 +1108,112 @@ impl CorpusManager {
         entry.feature_frequency = HashMap::new();
         entry.module_performance = HashMap::new();
         entry.module_features = HashMap::new();
+        self.corpus_hash.insert(dedup_key, true);
+        self.ir_hash_index.insert(hash_str(&entry.program_ir), entry.index);
+        self.mirror_to_bucket_store(&entry);
+        self.dedup_content(&mut entry);
+
+        if self.storage_mode == StorageMode::LessMemory {
+            let blob = CompressedBlob {
+                js_blob: compress_text(&entry.js_code),
+                ir_blob: compress_text(&entry.program_ir),
+            };
+            self.compressed_blobs.insert(entry.index, blob);
+            entry.js_code = String::new();
+            entry.program_ir = String::new();
+        } else if self.bucket_store.is_some() {
+            // Already durably mirrored above; drop the resident copy so this
+            // entry's footprint in `entries` is just its scalar fields plus
+            // program_ir, with js_code paged back in on demand by `materialize`.
+            entry.js_code = String::new();
+        }
         self.entries.push_back(entry);
-        
+
         //println!("[CORPUS DEBUG] Added new entry. Total entries: {}", self.entries.len());
         if self.entries.len() % 10 == 0 {
             //println!("[CORPUS DEBUG] Corpus now has {} entries", self.entries.len());
         }
+        self.rebuild_favored();
+        true
+    }
+
+    /// Same as `add_entry`, but also records which entry this one was
+    /// mutated from and which named pass produced it, so the corpus can
+    /// later be exported as a lineage graph.
+    pub fn add_entry_with_lineage(&mut self, mut entry: CorpusEntry, parent_index: Option<u32>, pass: Option<String>) {
+        entry.parent_index = parent_index;
+        entry.discovered_by_pass = pass;
+        if !self.add_entry(entry) {
+            return;
+        }
+        if self.lineage_path.is_some() {
+            self.dump_lineage_dot();
+        }
+    }
+
+    /// Stronger dedup mode for callers that already know which edges an
+    /// entry covers (e.g. right after a `cov_evaluate_hitcounts` run): skips
+    /// the insert entirely when `covered_edges` is a strict subset of any
+    /// existing entry's coverage, since every edge it covers is already
+    /// covered by that entry and it only adds weight to the
+    /// `select_next_input` scoring pass. Falls back to plain `add_entry`'s
+    /// hash-based dedup otherwise.
+    pub fn add_entry_if_not_subsumed(&mut self, entry: CorpusEntry, covered_edges: &[u64]) -> bool {
+        if !covered_edges.is_empty() {
+            let covered: HashSet<u64> = covered_edges.iter().copied().collect();
+            let subsumed = self.entries.iter().any(|existing| {
+                !existing.feature_frequency.is_empty()
+                    && covered.len() < existing.feature_frequency.len()
+                    && covered.iter().all(|edge| existing.feature_frequency.contains_key(edge))
+            });
+            if subsumed {
+                self.duplicates_suppressed += 1;
+                return false;
+            }
+        }
+
+        let index = self.entries.len() as u32;
+        if !self.add_entry(entry) {
+            return false;
+        }
+        if let Some(added) = self.entries.iter_mut().find(|e| e.index == index) {
+            for &edge in covered_edges {
+                added.feature_frequency.entry(edge).or_insert(0);
+            }
+        }
+        self.rebuild_favored();
+        true
+    }
+
+    /// Emits the corpus genealogy as a Graphviz DOT digraph: one node per
+    /// entry (labeled with its edge count and times used), one edge per
+    /// parent -> child relationship (labeled with the mutation pass name).
+    pub fn dump_lineage_dot(&self) {
+        let Some(ref path) = self.lineage_path else {
+            return;
+        };
+        let mut dot = String::new();
+        dot.push_str("digraph corpus_lineage {\n");
+        dot.push_str("    node [shape=box];\n");
+        for entry in &self.entries {
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"entry {}\\nedges={}\\nused={}\"];\n",
+                entry.index, entry.index, entry.coverage_found, entry.times_used
+            ));
+        }
+        for entry in &self.entries {
+            if let Some(parent) = entry.parent_index {
+                let pass = entry.discovered_by_pass.as_deref().unwrap_or("unknown");
+                dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                    parent, entry.index, pass
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        if let Err(e) = std::fs::write(path, dot) {
+            eprintln!("[CORPUS] Failed to write lineage graph to {}: {}", path.display(), e);
+        }
     }
     pub fn update_entry_success(&mut self, index: u32, new_coverage: u32) {
         if let Some(entry) = self.entries.iter_mut().find(|e| e.index == index) {
@@ -157,6 +1227,7 @@ impl CorpusManager {
         }
         self.last_new_coverage = Instant::now();
         self.total_coverage.insert(index as u64, new_coverage as u64);
+        self.refresh_entry_score(index);
     }
    
    
@@ -171,53 +1242,78 @@ impl CorpusManager {
     pub fn get_feature_count(&self, feature: u64) -> u64 {
         self.total_coverage.get(&feature).cloned().unwrap_or(0)
     }
+
+    /// Number of unique edges covered so far, for the admin/metrics endpoints.
+    pub fn edges_covered(&self) -> u64 {
+        self.global_edge_hits.len() as u64
+    }
     pub fn select_random_input(&mut self) -> Option<CorpusEntry> {
         let mut rng = rand::thread_rng();
         let index = rng.gen_range(0..self.entries.len());
-        Some(self.entries[index].clone())
+        let mut selected = self.entries[index].clone();
+        self.materialize(&mut selected);
+        Some(selected)
     }
     pub fn select_next_input(&mut self) -> Option<CorpusEntry> {
-        // return None;
-        let mut rng = rand::thread_rng();
-        
         // Increment selection counter
         self.selection_counter += 1;
-      
+
         // Dump stats periodically
         if self.selection_counter % 10000 == 0 {
             self.dump_stats_to_json();
+            if let Err(e) = self.flush_to_disk() {
+                eprintln!("[CORPUS] Failed to flush persisted corpus: {}", e);
+            }
         }
-        
+
+        // Check memory pressure more often than the full stats dump, since
+        // resident memory can climb well within one 10000-selection window.
+        if self.selection_counter % 1000 == 0 {
+            let evicted = self.prune_for_memory_pressure();
+            if evicted > 0 {
+                println!("[CORPUS] Pruned {} entries under memory pressure", evicted);
+            }
+        }
+
         // Check if corpus is empty
         if self.entries.is_empty() {
             //println!("[CORPUS DEBUG] Cannot select entry: corpus is empty");
             return None;
         }
-        
-        // Calculate scores for each entry
-        let scores: Vec<_> = self.entries.iter().enumerate()
-            .map(|(idx, entry)| {
-                let mut score = entry.performance_score;
-                
-                // Prioritize smaller code size (inverse relationship)
-                let size_factor = 1.0 / (1.0 + entry.js_code.len() as f64 * 0.001);
-                
-                // Reward success count and coverage found
-                let success_factor = 1.0 + entry.success_count as f64 * 0.2;
-                let coverage_factor = 1.0 + entry.coverage_found as f64 * 0.1;
-                
-                // Penalize errors and timeouts
-                let error_penalty = 1.0 / (1.0 + entry.error_count as f64 * 0.3);
-                let timeout_penalty = 1.0 / (1.0 + entry.timeout_count as f64 * 0.4);
-                
-                // Penalize overused entries (stronger penalty)
-                let usage_penalty = 1.0 / (1.0 + entry.times_used as f64 * 0.2);
-                
-                // Calculate final score combining all factors
-                score *= size_factor * success_factor * coverage_factor * error_penalty * timeout_penalty * usage_penalty;
-                
-                (idx, score)
-            })
+
+        match self.scheduler_mode {
+            SchedulerMode::Incremental => self.select_next_input_incremental(),
+            SchedulerMode::Linear => self.select_next_input_linear(),
+        }
+    }
+
+    /// Original O(n)-per-call weighted roulette: rescores every candidate
+    /// entry from scratch and walks the cumulative distribution. Kept around
+    /// (gated via `scheduler_mode`) so the incremental path can be compared
+    /// against it.
+    fn select_next_input_linear(&mut self) -> Option<CorpusEntry> {
+        let mut rng = rand::thread_rng();
+
+        // IndexesLenTimeMinimizerScheduler bias: draw from the favored
+        // (minimal edge-covering) set with high probability, and only fall
+        // back to the full corpus -- where every non-favored entry is
+        // effectively skippable -- the rest of the time. This keeps the
+        // working set close to a minimal edge cover without ever starving
+        // non-favored entries entirely.
+        let draw_from_favored = !self.favored.is_empty()
+            && rng.gen::<f64>() < Self::FAVORED_SELECTION_PROBABILITY;
+        let candidate_indices: Vec<usize> = if draw_from_favored {
+            self.entries.iter().enumerate()
+                .filter(|(_, entry)| entry.is_favored)
+                .map(|(idx, _)| idx)
+                .collect()
+        } else {
+            (0..self.entries.len()).collect()
+        };
+
+        // Calculate scores for each candidate entry
+        let scores: Vec<_> = candidate_indices.iter().copied()
+            .map(|idx| (idx, compute_score(&self.entries[idx], &self.global_edge_hits)))
             .collect();
 
         // Select entry based on scores
@@ -232,14 +1328,15 @@ impl CorpusManager {
             selection -= score;
             if selection <= 0.0 {
                 self.entries[idx].times_used += 1;
-                let selected_entry = self.entries[idx].clone();
-                
+                let mut selected_entry = self.entries[idx].clone();
+                self.materialize(&mut selected_entry);
+
                 // Log every 1000 selections
                 if self.selection_counter % 10000 == 0 {
-                    //println!("[CORPUS DEBUG] Selected entry {} (score: {:.2}). Times used: {}", 
+                    //println!("[CORPUS DEBUG] Selected entry {} (score: {:.2}). Times used: {}",
                             // selected_entry.index, score, selected_entry.times_used);
                 }
-                
+
                 return Some(selected_entry);
             }
         }
@@ -248,6 +1345,48 @@ impl CorpusManager {
         None
     }
 
+    /// Incremental priority-queue-style scheduler: samples in O(1) from a
+    /// lazily-built (O(n) to build) Walker alias table over each entry's
+    /// `cached_score`, instead of rescoring and re-walking the whole corpus
+    /// on every call; the sampled entry is then located by a linear scan of
+    /// `entries`. Keeps the
+    /// same favored-vs-full-corpus bias as the linear path via two
+    /// independently-cached tables.
+    fn select_next_input_incremental(&mut self) -> Option<CorpusEntry> {
+        let mut rng = rand::thread_rng();
+        let draw_from_favored = !self.favored.is_empty()
+            && rng.gen::<f64>() < Self::FAVORED_SELECTION_PROBABILITY;
+
+        let index = if draw_from_favored {
+            if self.alias_favored.is_none() {
+                let weighted: Vec<(u32, f64)> = self.entries.iter()
+                    .filter(|e| e.is_favored)
+                    .map(|e| (e.index, e.cached_score.max(f64::MIN_POSITIVE)))
+                    .collect();
+                self.alias_favored = AliasTable::build(&weighted);
+                self.alias_favored_energy = self.total_favored_energy;
+            }
+            self.alias_favored.as_ref().map(|table| table.sample(&mut rng))
+        } else {
+            if self.alias_all.is_none() {
+                let weighted: Vec<(u32, f64)> = self.entries.iter()
+                    .map(|e| (e.index, e.cached_score.max(f64::MIN_POSITIVE)))
+                    .collect();
+                self.alias_all = AliasTable::build(&weighted);
+                self.alias_all_energy = self.total_energy;
+            }
+            self.alias_all.as_ref().map(|table| table.sample(&mut rng))
+        };
+
+        let index = index?;
+        let pos = self.entries.iter().position(|e| e.index == index)?;
+        self.entries[pos].times_used += 1;
+        let mut selected_entry = self.entries[pos].clone();
+        self.materialize(&mut selected_entry);
+        self.refresh_entry_score(index);
+        Some(selected_entry)
+    }
+
     
    
     fn update_worker_files_json(&self) {
@@ -307,7 +1446,33 @@ impl CorpusManager {
         println!("\nCoverage Statistics:");
         println!("  Total edges covered: {}", total_coverage);
         println!("  Time since last new coverage: {:?}", self.last_new_coverage.elapsed());
-        
+        println!("  Favored set size: {}", self.favored.len());
+        println!("  Average rarity: {:.3}", self.avg_rarity);
+
+        // Storage mode
+        let (logical_bytes, resident_bytes) = self.storage_byte_totals();
+        println!("\nStorage Statistics:");
+        println!("  Mode: {:?}", self.storage_mode);
+        println!("  Logical bytes: {}", logical_bytes);
+        println!("  Resident bytes: {}", resident_bytes);
+
+        // Process memory (jemalloc)
+        match crate::mem_telemetry::sample() {
+            Ok(sample) => {
+                println!("\nProcess Memory (jemalloc):");
+                println!("  Resident: {}", sample.resident_bytes);
+                println!("  Allocated: {}", sample.allocated_bytes);
+                if let Some(high_water) = self.mem_high_water_bytes {
+                    println!("  High water mark: {}", high_water);
+                    println!("  Under memory pressure: {}", sample.resident_bytes > high_water);
+                }
+                println!("  Entries pruned for memory: {}", self.entries_pruned_for_memory);
+            }
+            Err(e) => {
+                println!("\nProcess Memory (jemalloc): unavailable ({})", e);
+            }
+        }
+
         // Bytecode stats
         if let Some((patterns, instructions, functions, analyses)) = self.get_bytecode_stats() {
             println!("\nBytecode Analysis Statistics:");
@@ -436,6 +1601,11 @@ impl CorpusManager {
         
         let mut rng = rand::thread_rng();
         let index = rng.gen_range(0..self.entries.len());
+        if self.storage_mode == StorageMode::LessMemory {
+            if let Some(blob) = self.compressed_blobs.get(&self.entries[index].index) {
+                return decompress_text(&blob.ir_blob);
+            }
+        }
         self.entries[index].program_ir.clone()
     }
 
@@ -481,7 +1651,9 @@ impl CorpusManager {
         
         // Count total coverage found across all entries
         let total_coverage_found: u32 = self.entries.iter().map(|e| e.coverage_found).sum();
-        
+
+        let (logical_bytes, resident_bytes) = self.storage_byte_totals();
+
         // Build statistics to dump
         let stats = serde_json::json!({
             "timestamp": std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs(),
@@ -497,6 +1669,30 @@ impl CorpusManager {
                 "success_rate": if self.stats.total_mutations > 0 {
                     self.stats.successful_mutations as f64 / self.stats.total_mutations as f64
                 } else { 0.0 },
+                "favored_set_size": self.favored.len(),
+                "avg_rarity": self.avg_rarity,
+                "storage_mode": match self.storage_mode {
+                    StorageMode::LessTime => "less_time",
+                    StorageMode::LessMemory => "less_memory",
+                },
+                "logical_bytes": logical_bytes,
+                "resident_bytes": resident_bytes,
+                "unique_content_count": self.content_dedup_stats().0,
+                "content_dedup_ratio": {
+                    let (unique, total) = self.content_dedup_stats();
+                    if total > 0 { 1.0 - (unique as f64 / total as f64) } else { 0.0 }
+                },
+                "corrupted_entries_dropped": self.corrupted_entries_dropped,
+                "process_memory": match crate::mem_telemetry::sample() {
+                    Ok(sample) => serde_json::json!({
+                        "resident_bytes": sample.resident_bytes,
+                        "allocated_bytes": sample.allocated_bytes,
+                        "high_water_bytes": self.mem_high_water_bytes,
+                        "under_memory_pressure": self.mem_high_water_bytes.map(|h| sample.resident_bytes > h),
+                    }),
+                    Err(_) => serde_json::Value::Null,
+                },
+                "entries_pruned_for_memory": self.entries_pruned_for_memory,
             },
             "entry_statistics": self.calculate_entry_statistics()
         });
@@ -568,26 +1764,26 @@ impl CorpusManager {
         
         // Find min and max sizes
         for entry in &self.entries {
-            let size = entry.js_code.len();
+            let size = entry.logical_js_len;
             min_size = min_size.min(size);
             max_size = max_size.max(size);
         }
-        
+
         // Calculate bucket size
         let range = if max_size > min_size { max_size - min_size } else { 1 };
         let bucket_size = range / 5 + 1;
-        
+
         // Count entries in each bucket
         for entry in &self.entries {
-            let size = entry.js_code.len();
+            let size = entry.logical_js_len;
             let bucket = ((size - min_size) / bucket_size).min(4);
             size_buckets[bucket] += 1;
         }
-        
+
         serde_json::json!({
             "min_size": min_size,
             "max_size": max_size,
-            "average_size": self.entries.iter().map(|e| e.js_code.len()).sum::<usize>() as f64 / self.entries.len() as f64,
+            "average_size": self.entries.iter().map(|e| e.logical_js_len).sum::<usize>() as f64 / self.entries.len() as f64,
             "buckets": size_buckets,
             "bucket_size": bucket_size
         })
@@ -640,7 +1836,7 @@ impl CorpusManager {
         
         for entry in &self.entries {
             // Calculate the factors used in selection
-            let size_factor = 1.0 / (1.0 + entry.js_code.len() as f64 * 0.001);
+            let size_factor = 1.0 / (1.0 + entry.logical_js_len as f64 * 0.001);
             let success_factor = 1.0 + entry.success_count as f64 * 0.2;
             let coverage_factor = 1.0 + entry.coverage_found as f64 * 0.1;
             let error_penalty = 1.0 / (1.0 + entry.error_count as f64 * 0.3);
@@ -686,26 +1882,30 @@ impl CorpusManager {
         })
     }
     
+    /// The size/success/coverage/error/timeout/usage factor product used to
+    /// rank entries for both `get_top_entries` (highest first, for stats) and
+    /// `prune_for_memory_pressure` (lowest first, for eviction), so the two
+    /// can't drift apart into two different notions of "low-value entry".
+    fn retention_score(entry: &CorpusEntry) -> f64 {
+        let size_factor = 1.0 / (1.0 + entry.logical_js_len as f64 * 0.001);
+        let success_factor = 1.0 + entry.success_count as f64 * 0.2;
+        let coverage_factor = 1.0 + entry.coverage_found as f64 * 0.1;
+        let error_penalty = 1.0 / (1.0 + entry.error_count as f64 * 0.3);
+        let timeout_penalty = 1.0 / (1.0 + entry.timeout_count as f64 * 0.4);
+        let usage_penalty = 1.0 / (1.0 + entry.times_used as f64 * 0.2);
+
+        entry.performance_score * size_factor * success_factor *
+            coverage_factor * error_penalty * timeout_penalty * usage_penalty
+    }
+
     fn get_top_entries(&self, count: usize) -> serde_json::Value {
         if self.entries.is_empty() {
             return serde_json::json!([]);
         }
-        
+
         // Calculate scores for all entries
         let mut entry_scores: Vec<(u32, f64, usize)> = self.entries.iter().enumerate()
-            .map(|(idx, entry)| {
-                let size_factor = 1.0 / (1.0 + entry.js_code.len() as f64 * 0.001);
-                let success_factor = 1.0 + entry.success_count as f64 * 0.2;
-                let coverage_factor = 1.0 + entry.coverage_found as f64 * 0.1;
-                let error_penalty = 1.0 / (1.0 + entry.error_count as f64 * 0.3);
-                let timeout_penalty = 1.0 / (1.0 + entry.timeout_count as f64 * 0.4);
-                let usage_penalty = 1.0 / (1.0 + entry.times_used as f64 * 0.2);
-                
-                let score = entry.performance_score * size_factor * success_factor * 
-                           coverage_factor * error_penalty * timeout_penalty * usage_penalty;
-                
-                (entry.index, score, idx)
-            })
+            .map(|(idx, entry)| (entry.index, Self::retention_score(entry), idx))
             .collect();
         
         // Sort by score (highest first)
@@ -719,7 +1919,7 @@ impl CorpusManager {
                 serde_json::json!({
                     "index": index,
                     "score": score,
-                    "js_code_size": entry.js_code.len(),
+                    "js_code_size": entry.logical_js_len,
                     "times_used": entry.times_used,
                     "success_count": entry.success_count,
                     "coverage_found": entry.coverage_found,
@@ -757,34 +1957,42 @@ impl CorpusManager {
         if let Some(entry) = self.entries.iter_mut().find(|e| e.index == index) {
             entry.error_count += 1;
             entry.last_used = Instant::now();
-            
+
             // Update performance score to penalize errors
             // Reduce performance score by 5% for each error
             entry.performance_score *= 0.95;
-            //println!("[CORPUS DEBUG] Entry {} error count increased to {}. New performance score: {:.2}", 
+            //println!("[CORPUS DEBUG] Entry {} error count increased to {}. New performance score: {:.2}",
                     // index, entry.error_count, entry.performance_score);
         } else {
             //println!("[CORPUS DEBUG] Failed to update error for entry {}: not found", index);
         }
-        
+        if let Some(entry) = self.entries.iter().find(|e| e.index == index) {
+            self.mirror_meta_update(entry);
+        }
+        self.refresh_entry_score(index);
+
         // Record this mutation as unsuccessful
         self.record_mutation_result(false);
     }
-    
+
     pub fn update_entry_timeout(&mut self, index: u32) {
         if let Some(entry) = self.entries.iter_mut().find(|e| e.index == index) {
             entry.timeout_count += 1;
             entry.last_used = Instant::now();
-            
+
             // Update performance score to penalize timeouts
             // Reduce performance score by 10% for each timeout
             entry.performance_score *= 0.90;
-            //println!("[CORPUS DEBUG] Entry {} timeout count increased to {}. New performance score: {:.2}", 
+            //println!("[CORPUS DEBUG] Entry {} timeout count increased to {}. New performance score: {:.2}",
                     // index, entry.timeout_count, entry.performance_score);
         } else {
             //println!("[CORPUS DEBUG] Failed to update timeout for entry {}: not found", index);
         }
-        
+        if let Some(entry) = self.entries.iter().find(|e| e.index == index) {
+            self.mirror_meta_update(entry);
+        }
+        self.refresh_entry_score(index);
+
         // Record this mutation as unsuccessful
         self.record_mutation_result(false);
     }
@@ -836,9 +2044,100 @@ impl CorpusManager {
         if has_new_coverage {
             return true;
         }
-        
+
+        // Under memory pressure, bytecode novelty alone isn't enough to earn
+        // a slot -- only genuinely new coverage does.
+        if self.under_memory_pressure() {
+            return false;
+        }
+
         // If no new coverage, check for bytecode novelty
         self.analyze_bytecode_novelty(entry)
     }
 
+    /// Evicts the lowest-`retention_score` entries until estimated resident
+    /// usage is back down to `mem_low_water_bytes`. No-op unless
+    /// `set_memory_pressure_thresholds` was called and jemalloc currently
+    /// reports resident bytes above the high water mark. Returns the number
+    /// of entries evicted.
+    pub fn prune_for_memory_pressure(&mut self) -> usize {
+        let (Some(high_water), Some(low_water)) = (self.mem_high_water_bytes, self.mem_low_water_bytes) else {
+            return 0;
+        };
+        let resident_bytes = match crate::mem_telemetry::sample() {
+            Ok(sample) => sample.resident_bytes,
+            Err(_) => return 0,
+        };
+        if resident_bytes <= high_water {
+            return 0;
+        }
+
+        // Single jemalloc sample up front; each candidate entry's own
+        // logical size then stands in for how much eviction would free, so
+        // the prune loop doesn't need to re-sample the allocator per entry.
+        let mut to_free = resident_bytes.saturating_sub(low_water);
+        let mut ranked: Vec<(u32, f64, u64)> = self.entries.iter()
+            .map(|e| (e.index, Self::retention_score(e), (e.logical_js_len + e.logical_ir_len) as u64))
+            .collect();
+        ranked.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut evicted = 0;
+        for (index, _score, logical_bytes) in ranked {
+            if to_free == 0 || self.entries.len() <= 1 {
+                break;
+            }
+            self.release_content(index);
+            self.compressed_blobs.remove(&index);
+            self.delete_entry(index);
+            to_free = to_free.saturating_sub(logical_bytes);
+            evicted += 1;
+        }
+        if evicted > 0 {
+            self.entries_pruned_for_memory += evicted as u64;
+            self.rebuild_favored();
+        }
+        evicted
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two entries sharing identical `js_code` (so `dedup_content` clears
+    /// one of them in memory) must both still round-trip their full content
+    /// through `flush_to_disk`/`set_persist_dir` -- regression test for
+    /// `flush_to_disk` having once written the deduped entry's `js_code`
+    /// straight from the (cleared) in-memory field instead of materializing
+    /// it first.
+    #[test]
+    fn flush_to_disk_persists_deduped_content() {
+        let dir = std::env::temp_dir().join(format!(
+            "rustrunner_corpus_dedup_test_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos()
+        ));
+
+        let shared_js = "function f() { return 1; }".to_string();
+        let mut manager = CorpusManager::new(0, 10000);
+        manager.set_persist_dir(dir.clone()).unwrap();
+        assert!(manager.add_entry(CorpusEntry::new("{\"type\":\"A\"}".to_string(), shared_js.clone())));
+        assert!(manager.add_entry(CorpusEntry::new("{\"type\":\"B\"}".to_string(), shared_js.clone())));
+        // Confirm dedup actually cleared the second entry's in-memory js_code,
+        // otherwise this test wouldn't be exercising the bug it guards against.
+        assert_eq!(manager.entries[1].js_code, "");
+        manager.flush_to_disk().unwrap();
+
+        let mut reloaded = CorpusManager::new(0, 10000);
+        reloaded.set_persist_dir(dir.clone()).unwrap();
+        assert_eq!(reloaded.entries.len(), 2);
+        for entry in &reloaded.entries {
+            let mut materialized = entry.clone();
+            reloaded.materialize(&mut materialized);
+            assert_eq!(materialized.js_code, shared_js);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
\ No newline at end of file
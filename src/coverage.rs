@@ -1,6 +1,5 @@
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::collections::HashSet;
 
 use std::ptr;
 
@@ -16,9 +15,81 @@ impl  EdgeSet {
    pub fn new() -> Self {
     EdgeSet { count: 0, edge_indices: ptr::null_mut() }
    }
-  
+
+}
+
+/// Where an `OwnedEdgeSet`'s buffer came from, so `Drop` can release it the
+/// right way: a buffer `cov_evaluate` allocated on the native side needs
+/// `cov_free_edge_set`, while one computed in Rust (e.g. an intersection)
+/// just needs its `Vec` to drop normally.
+enum EdgeBacking {
+    Native,
+    Owned(Vec<u32>),
+}
+
+/// An owning `EdgeSet`: pairs the raw `#[repr(C)]` view with enough
+/// information to free its buffer exactly once via `Drop`, instead of
+/// relying on callers to remember to (which `extract_testcase_coverage`
+/// didn't — it `forget`'d every intersection buffer it produced, and the
+/// native buffers `cov_evaluate` allocates were never freed at all).
+pub struct OwnedEdgeSet {
+    edges: EdgeSet,
+    backing: EdgeBacking,
 }
-#[derive(PartialEq,Debug)]
+
+impl OwnedEdgeSet {
+    /// Runs `cov_evaluate` for `worker_id`, taking ownership of the native
+    /// buffer it allocates.
+    pub fn from_native(worker_id: usize) -> Self {
+        let mut edges = EdgeSet::new();
+        unsafe {
+            crate::cov_evaluate(worker_id, &mut edges);
+        }
+        OwnedEdgeSet { edges, backing: EdgeBacking::Native }
+    }
+
+    /// Wraps edge indices already owned by Rust (e.g. a computed
+    /// intersection), with no native buffer to free.
+    pub fn from_vec(mut indices: Vec<u32>) -> Self {
+        let edges = EdgeSet { count: indices.len() as u32, edge_indices: indices.as_mut_ptr() };
+        OwnedEdgeSet { edges, backing: EdgeBacking::Owned(indices) }
+    }
+
+    pub fn empty() -> Self {
+        Self::from_vec(Vec::new())
+    }
+
+    pub fn count(&self) -> u32 {
+        self.edges.count
+    }
+
+    pub fn as_slice(&self) -> &[u32] {
+        if self.edges.count == 0 || self.edges.edge_indices.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.edges.edge_indices, self.edges.count as usize) }
+        }
+    }
+
+    /// Borrow as the `#[repr(C)]` view existing helpers (`reset_edge_set`,
+    /// `mark_edge_set`, `cov_evaluate_hitcounts`) expect.
+    pub fn as_edge_set_mut(&mut self) -> &mut EdgeSet {
+        &mut self.edges
+    }
+}
+
+impl Drop for OwnedEdgeSet {
+    fn drop(&mut self) {
+        if let EdgeBacking::Native = self.backing {
+            if !self.edges.edge_indices.is_null() {
+                unsafe {
+                    crate::cov_free_edge_set(&mut self.edges);
+                }
+            }
+        }
+    }
+}
+#[derive(PartialEq,Debug,Clone)]
 pub enum ResultCode {
     Success,
     Timeout,
@@ -43,10 +114,12 @@ unsafe extern "C" {
     pub fn cov_clear_edge_data(worker_id: usize, index: u32);
     pub fn cov_set_edge_data(worker_id: usize, index: u32);
     pub fn reprl_fetch_stdout(worker_id: i32) -> *mut i8;
-    pub fn cleanup_reprl(worker_id: i32); 
+    pub fn reprl_fetch_stderr(worker_id: i32) -> *mut i8;
+    pub fn cleanup_reprl(worker_id: i32);
     pub fn cov_fetch_cmp_events(worker_id: i32) -> *mut CmpEvent;
     pub fn fetch_event_count(worker_id: i32) -> u64;
     pub fn cov_clear_cmp_events(worker_id: i32);
+    pub fn cov_free_edge_set(edges: *mut EdgeSet);
 }
 pub fn reset_edge_set(worker_id: usize, edge_set: &mut EdgeSet) {
     for i in 0..edge_set.count {
@@ -66,177 +139,317 @@ pub fn mark_edge_set(worker_id: usize, edge_set: &mut EdgeSet) {
 }
 
 pub fn get_result_code(result_code: i32) -> ResultCode {
-    if result_code == 0 {
-        return ResultCode::Success;
+    let profile = std::env::var("PROFILE").unwrap_or_else(|_| "v8".to_string());
+    get_result_code_for_profile(result_code, &profile)
+}
+
+/// Same classification as `get_result_code`, but against an explicit
+/// `profile` instead of the process-wide `PROFILE` env var. Exists so
+/// callers comparing several engines at once (see `crate::differential`)
+/// can classify each engine's result against its own profile rather than
+/// whatever `PROFILE` happens to be set to in this process. Dispatches
+/// through `crate::engine_profile`'s registry rather than hardcoding the
+/// per-engine crash codes here, so adding an engine is registering a
+/// profile instead of editing this function.
+pub fn get_result_code_for_profile(result_code: i32, profile: &str) -> ResultCode {
+    crate::engine_profile::classify(profile, result_code)
+}
+pub fn init_reprl_safe(worker_id: usize) {
+    unsafe {
+        init(worker_id as i32);
+        spawn(worker_id as i32);
+        coverage_finish_initialization(worker_id, 0);
+    }
+}
+/// Log2-ish hitcount buckets, AFL/LibAFL style: 0 (never hit), 1, 2, 3,
+/// 4-7, 8-15, 16-31, 32-127, 128+.
+fn hitcount_bucket(hits: u32) -> u8 {
+    match hits {
+        0 => 0,
+        1 => 1,
+        2 => 2,
+        3 => 3,
+        4..=7 => 4,
+        8..=15 => 5,
+        16..=31 => 6,
+        32..=127 => 7,
+        _ => 8,
     }
+}
+
+/// Per-worker hitcount state: cumulative hits per edge id, plus a bitmask
+/// (one bit per bucket from `hitcount_bucket`) of which buckets have ever
+/// been observed for that edge — the AFL/LibAFL "virgin map", except keyed
+/// by (edge, bucket) instead of just (edge).
+#[derive(Default)]
+struct HitcountState {
+    hits: std::collections::HashMap<u32, u32>,
+    virgin_buckets: std::collections::HashMap<u32, u16>,
+}
+
+/// Every worker thread calls `cov_evaluate_hitcounts` concurrently on its own
+/// `worker_id`, so the backing `Vec` needs real synchronization: an unguarded
+/// `static mut` can have one thread's `resize_with` reallocate the buffer
+/// while another holds a reference into the old allocation. One `Mutex`
+/// around the whole `Vec` (rather than per-worker locks pre-sized to
+/// `NUM_WORKERS`, which isn't known at this static's init time) keeps the
+/// resize-then-index sequence atomic; the critical section is just a hash
+/// map bump per edge, so contention isn't a concern.
+static HITCOUNT_STATE: std::sync::Mutex<Vec<HitcountState>> = std::sync::Mutex::new(Vec::new());
 
-    if result_code == 65536 {
-        return ResultCode::Timeout;
+/// Evaluates coverage for the just-completed execution like `cov_evaluate`,
+/// but layers AFL/LibAFL-style hitcount bucketing on top: every edge
+/// present in this run's `EdgeSet` gets its cumulative hit counter bumped,
+/// is placed into one of the nine `hitcount_bucket` buckets, and counts as
+/// novel if that bucket has never been reached by this edge before — not
+/// merely if the edge itself is new. The counter counts executions in which
+/// the edge was reported, not how many times the edge ran within one
+/// execution: the `EdgeSet` carries no per-run hit counts, so a loop that
+/// runs 40 times instead of 3 in a single run does not move its bucket.
+///
+/// Callers that want repeat visibility into already-discovered edges must
+/// `reset_edge_set` the edges afterwards (mirroring the minimize-then-remark
+/// pattern used elsewhere in this module); otherwise the underlying virgin
+/// bitmap on the C side will never report that edge again and it can only
+/// ever reach bucket 1.
+pub fn cov_evaluate_hitcounts(worker_id: usize, edges: &mut EdgeSet) -> u32 {
+    unsafe {
+        crate::cov_evaluate(worker_id, edges);
     }
-    let profile = std::env::var("PROFILE").unwrap_or_else(|_| "v8".to_string());
-    if profile == "v8" {
-        if result_code == 5 || result_code == 6 || result_code == 11 {
-            return ResultCode::Crash;
+    let mut all_state = HITCOUNT_STATE.lock().unwrap();
+    if all_state.len() <= worker_id {
+        all_state.resize_with(worker_id + 1, HitcountState::default);
+    }
+    let state = &mut all_state[worker_id];
+    let mut transitioned = 0u32;
+    for i in 0..edges.count {
+        let edge_idx = unsafe { *edges.edge_indices.add(i as usize) };
+        let hits = state.hits.entry(edge_idx).or_insert(0);
+        *hits += 1;
+        let bucket = hitcount_bucket(*hits);
+        let seen = state.virgin_buckets.entry(edge_idx).or_insert(0);
+        let bit = 1u16 << bucket;
+        if *seen & bit == 0 {
+            *seen |= bit;
+            transitioned += 1;
         }
-        return ResultCode::Error;
     }
+    transitioned
+}
 
-    if profile == "gecko" {
-        if result_code == 256 {
-            return ResultCode::Crash;
+/// Edge indices kept sorted and de-duplicated so intersection, containment
+/// ratio, and superset checks are each a single linear merge (or, for
+/// single-membership tests, a binary search) instead of building a
+/// `HashSet` per call or running a nested O(n × n) scan.
+#[derive(Clone, Debug, Default)]
+pub struct CoverageSet(Vec<u32>);
+
+impl CoverageSet {
+    pub fn from_slice(indices: &[u32]) -> Self {
+        let mut sorted = indices.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        CoverageSet(sorted)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn as_slice(&self) -> &[u32] {
+        &self.0
+    }
+
+    pub fn into_vec(self) -> Vec<u32> {
+        self.0
+    }
+
+    /// Edges present in both sets, via a single linear merge over the two
+    /// sorted slices.
+    pub fn intersect(&self, other: &CoverageSet) -> Vec<u32> {
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.0.len() && j < other.0.len() {
+            match self.0[i].cmp(&other.0[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    result.push(self.0[i]);
+                    i += 1;
+                    j += 1;
+                }
+            }
         }
+        result
     }
-    if profile == "jsc" {
-        if result_code == 256 || result_code == 6 || result_code == 11 {
-            return ResultCode::Crash;
+
+    /// Whether any edge is present in both sets, short-circuiting on the
+    /// first match instead of materializing the full intersection.
+    pub fn intersects(&self, other: &CoverageSet) -> bool {
+        let (mut i, mut j) = (0, 0);
+        while i < self.0.len() && j < other.0.len() {
+            match self.0[i].cmp(&other.0[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => return true,
+            }
         }
+        false
     }
 
-    ResultCode::Error
-}
-pub fn init_reprl_safe(worker_id: usize) {
-    unsafe {
-        init(worker_id as i32);
-        spawn(worker_id as i32);
-        coverage_finish_initialization(worker_id, 0);
+    /// Fraction of `self`'s edges that are also present in `other`.
+    pub fn contains_ratio(&self, other: &CoverageSet) -> f32 {
+        if self.0.is_empty() {
+            return 0.0;
+        }
+        self.intersect(other).len() as f32 / self.0.len() as f32
+    }
+
+    /// Whether every edge in `other` is also present in `self`.
+    pub fn is_superset(&self, other: &CoverageSet) -> bool {
+        other.0.iter().all(|edge| self.0.binary_search(edge).is_ok())
     }
 }
-pub fn v8_reprl_check(worker_id: i32){
-
-    let test_code = "var x = 1;";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 100, 0, worker_id) };
-    println!("Success result: {}", result);
-    assert_eq!(get_result_code(result), ResultCode::Success);
-    // Check timeout
-    let test_code = "while(true){}";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 100, 0, worker_id) };
-    println!("Timeout result: {}", result);
-    assert_eq!(get_result_code(result), ResultCode::Timeout); //timeout code
-
-    let test_code = "var x =";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 1000, 0, worker_id) };
-    println!("Error result: {}", result);
-    assert_eq!(get_result_code(result), ResultCode::Error); //error code
-
-    let test_code = "fuzzilli('FUZZILLI_CRASH', 0);";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 1000, 0, worker_id) };
-    println!("Crash result: {}", result);
-    assert_eq!(get_result_code(result), ResultCode::Crash);
-
-    let test_code = "fuzzilli('FUZZILLI_CRASH', 1);";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 1000, 0, worker_id) };
-    println!("Crash result: {}", result);
-    assert_eq!(get_result_code(result), ResultCode::Crash);
-
-    let test_code = "fuzzilli('FUZZILLI_CRASH', 2);";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 1000, 0, worker_id) };
-    println!("Crash result: {}", result);
-    assert_eq!(get_result_code(result), ResultCode::Crash);
-
-    // let test_code = "fuzzilli('FUZZILLI_CRASH', 3);";
-    // let test_code = format!("{}\x00", test_code);
-    // let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 1000, 0, worker_id) };
-    // println!("result: {}", result);
-    // assert_eq!(get_result_code(result), ResultCode::Crash);
-
-    // let test_code = "fuzzilli('FUZZILLI_CRASH', 8);";
-    // let test_code = format!("{}\x00", test_code);
-    // let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 1000 , 0, worker_id) };
-    // assert_eq!(get_result_code(result), ResultCode::Crash);
 
 
-}
-pub fn gecko_reprl_check(worker_id: i32){
-    let test_code = "var x = 1;";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 100, 0, worker_id) };
-    assert_eq!(get_result_code(result), ResultCode::Success);
-    // Check timeout
-    let test_code = "while(true){}";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 100, 0, worker_id) };
-    assert_eq!(get_result_code(result), ResultCode::Timeout); //timeout code
-
-    let test_code = "var x =";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 1000, 0, worker_id) };
-    assert_eq!(get_result_code(result), ResultCode::Error); //error code
-
-    let test_code = "fuzzilli('FUZZILLI_CRASH', 0);";
-    let test_code = format!("{}\x00", test_code);
-    println!("test_code: {}", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 1000, 0, worker_id) };
-    assert_eq!(get_result_code(result), ResultCode::Crash);
-
-    let test_code = "fuzzilli('FUZZILLI_CRASH', 1);";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 1000, 0, worker_id) };
-    assert_eq!(get_result_code(result), ResultCode::Crash);
-
-    let test_code = "fuzzilli('FUZZILLI_CRASH', 2);";
-    let test_code = format!("{}\x00", test_code);
-    let result = unsafe { execute_script(test_code.as_ptr() as *mut i8, 1000, 0, worker_id) };
-    assert_eq!(get_result_code(result), ResultCode::Crash);
+/// How many of `M` repeated runs an edge must be observed in to be kept as
+/// real coverage rather than discarded as run-to-run noise (GC, JIT
+/// tiering, ...). `Strict` (K=M) is what `extract_testcase_coverage` used to
+/// hardcode via set intersection, appropriate for minimization where losing
+/// a genuinely-covered edge is costly; `Lenient` tolerates some
+/// nondeterminism, which suits corpus admission better.
+#[derive(Debug, Clone, Copy)]
+pub enum StabilizationPolicy {
+    Strict,
+    Lenient,
 }
 
-pub fn common_subset(set1: &mut [u32], set2: &mut [u32]) -> Vec<u32> {
-    let set1: HashSet<_> = set1.iter().copied().collect();
-    set2.iter()
-        .copied()
-        .filter(|idx| set1.contains(idx))
-        .collect()
+impl StabilizationPolicy {
+    fn quorum(self, runs: u32) -> u32 {
+        match self {
+            StabilizationPolicy::Strict => runs,
+            StabilizationPolicy::Lenient => ((runs as f32 * 0.6).ceil() as u32).max(1),
+        }
+    }
 }
 
+/// Result of stabilizing coverage over several runs: the edges that met the
+/// quorum, plus how large a fraction of the runs actually observed each kept
+/// edge, so callers can inspect how stable a case really was instead of just
+/// a pass/fail kept set.
+pub struct StabilizedCoverage {
+    pub edges: OwnedEdgeSet,
+    pub stability: std::collections::HashMap<u32, f32>,
+}
 
-/// Extract coverage of a testcase with proper initialization
-pub fn extract_testcase_coverage(
+/// Runs `js_code` `runs` times, tracking how many of those runs observe each
+/// of `mutated_edges`, and keeps the ones observed at least `policy`'s
+/// quorum of times instead of requiring unanimous agreement. Breaks early
+/// once the kept set stops changing between runs.
+pub fn stabilize_coverage(
     js_code: &str,
     worker_id: usize,
     mutated_edges: &EdgeSet,
-) -> EdgeSet {
-    let test_code = js_code;
-    let mut edges = mutated_edges.clone();
-    
-    // Run the test multiple times and collect common edges
-    let mut last_common_len = 0;
-    for _ in 0..5 {
-        unsafe {
-            crate::execute_script(test_code.as_ptr() as *mut i8, crate::MAX_TIMEOUT, 0, worker_id as i32);
-        }
-        let mut new_edges = EdgeSet::new();
+    policy: StabilizationPolicy,
+    runs: u32,
+) -> StabilizedCoverage {
+    let candidates: std::collections::HashSet<u32> =
+        (0..mutated_edges.count).map(|i| unsafe { *mutated_edges.edge_indices.add(i as usize) }).collect();
+    let quorum = policy.quorum(runs);
+
+    accumulate_stabilized_coverage(runs, quorum, &candidates, |_run| {
         unsafe {
-            crate::cov_evaluate(worker_id, &mut new_edges);
+            crate::execute_script(js_code.as_ptr() as *mut i8, crate::MAX_TIMEOUT, 0, worker_id as i32);
         }
-        reset_edge_set(worker_id, &mut new_edges);
-        // Convert edge indices to Vec<u32> for common_subset calculation
-        let common = if edges.count > 0 && new_edges.count > 0 {
-            let edges_slice = unsafe { std::slice::from_raw_parts_mut(edges.edge_indices, edges.count as usize) };
-            let new_edges_slice = unsafe { std::slice::from_raw_parts_mut(new_edges.edge_indices, new_edges.count as usize) };
-            common_subset(edges_slice, new_edges_slice)
-        } else {
-            Vec::new()
-        };
-        
-        // Update edges with common subset
-        if !common.is_empty() {
-            if last_common_len == common.len() {
-               break;
+        let mut new_edges = OwnedEdgeSet::from_native(worker_id);
+        reset_edge_set(worker_id, new_edges.as_edge_set_mut());
+        new_edges.as_slice().to_vec()
+    })
+}
+
+/// The actual quorum bookkeeping `stabilize_coverage` runs per execution,
+/// factored out so it can be exercised with synthetic per-run edge
+/// observations instead of a real engine execution. `run_once(run_index)`
+/// returns the edges observed on that run.
+fn accumulate_stabilized_coverage(
+    runs: u32,
+    quorum: u32,
+    candidates: &std::collections::HashSet<u32>,
+    mut run_once: impl FnMut(u32) -> Vec<u32>,
+) -> StabilizedCoverage {
+    let mut hits: std::collections::HashMap<u32, u32> = std::collections::HashMap::new();
+
+    let mut observed_runs = 0u32;
+    let mut last_kept_len = usize::MAX;
+    for run in 0..runs {
+        let edges_this_run = run_once(run);
+        observed_runs += 1;
+        for edge in edges_this_run {
+            if candidates.contains(&edge) {
+                *hits.entry(edge).or_insert(0) += 1;
             }
-            last_common_len = common.len();
-            edges.count = common.len() as u32;
-            edges.edge_indices = common.as_ptr() as *mut u32;
-            std::mem::forget(common); // Prevent deallocation since we're using the raw pointer
         }
-        
+
+        let kept_len = hits.values().filter(|&&count| count >= quorum).count();
+        // No edge can reach `quorum` hits before `quorum` runs have even
+        // happened, so `kept_len` is trivially 0 and unchanged across those
+        // early iterations -- only treat that as real convergence once
+        // enough runs have occurred for the quorum to be reachable.
+        if observed_runs >= quorum && kept_len == last_kept_len {
+            break;
+        }
+        last_kept_len = kept_len;
     }
-    
-    edges
+
+    let kept: Vec<u32> = hits.iter().filter(|&(_, &count)| count >= quorum).map(|(&edge, _)| edge).collect();
+    let stability =
+        kept.iter().map(|&edge| (edge, hits[&edge] as f32 / observed_runs.max(1) as f32)).collect();
+
+    StabilizedCoverage { edges: OwnedEdgeSet::from_vec(kept), stability }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Regression test for the early-break firing before `quorum` runs had
+    /// even happened: under `Strict` (quorum == runs == 5), an edge hit on
+    /// every run must still survive, not get discarded because `kept_len`
+    /// looked "stable" at 0 after the first couple of iterations.
+    #[test]
+    fn strict_quorum_keeps_edge_hit_every_run() {
+        let mut candidates = std::collections::HashSet::new();
+        candidates.insert(7u32);
+        let quorum = StabilizationPolicy::Strict.quorum(5);
+        let result = accumulate_stabilized_coverage(5, quorum, &candidates, |_run| vec![7]);
+        assert_eq!(result.edges.as_slice(), &[7]);
+    }
+
+    #[test]
+    fn strict_quorum_drops_edge_missed_on_one_run() {
+        let mut candidates = std::collections::HashSet::new();
+        candidates.insert(7u32);
+        let quorum = StabilizationPolicy::Strict.quorum(5);
+        let result = accumulate_stabilized_coverage(5, quorum, &candidates, |run| if run == 2 { vec![] } else { vec![7] });
+        assert!(result.edges.as_slice().is_empty());
+    }
+}
+
+/// Extract coverage of a testcase with proper initialization, using a
+/// strict (K=M) stabilization quorum over 5 runs — the same behavior
+/// `extract_testcase_coverage` has always had, now expressed via
+/// `stabilize_coverage` so minimization and corpus admission can ask for a
+/// looser quorum instead.
+pub fn extract_testcase_coverage(
+    js_code: &str,
+    worker_id: usize,
+    mutated_edges: &EdgeSet,
+) -> OwnedEdgeSet {
+    stabilize_coverage(js_code, worker_id, mutated_edges, StabilizationPolicy::Strict, 5).edges
 }
 
 
@@ -246,69 +459,52 @@ pub fn maintain_coverage_with_mutated_edges(
     mutated_edges: &EdgeSet,
 ) -> (bool, bool) {
     let test_code = js_code;
-    let mut edges = mutated_edges.clone();
+    let edges = CoverageSet::from_slice(
+        &(0..mutated_edges.count).map(|i| unsafe { *mutated_edges.edge_indices.add(i as usize) }).collect::<Vec<_>>(),
+    );
     let mut is_new_coverage = false;
-    for i in 0..5 {
-        unsafe {
-            let result =crate::execute_script(
+    for _ in 0..5 {
+        let result = unsafe {
+            crate::execute_script(
                 test_code.as_ptr() as *mut i8,
                 crate::MAX_TIMEOUT,
                 0,
                 worker_id as i32,
-            );
-            let mut new_edges = EdgeSet::new();
-            crate::cov_evaluate(worker_id, &mut new_edges);
-            reset_edge_set(worker_id, &mut new_edges);
-            if get_result_code(result) == ResultCode::Crash {
-                is_new_coverage = true;
-            }
-            if get_result_code(result) == ResultCode::Success {
-                if new_edges.count > edges.count {
-                    is_new_coverage = true;
-                }
-                let mut is_found = false;
-                for i in 0..new_edges.count {
-                    let edge_idx = unsafe { *new_edges.edge_indices.add(i as usize) };
-                    for j in 0..edges.count {
-                        if unsafe { *edges.edge_indices.add(j as usize) } == edge_idx {
-                            is_found = true;
-                            break;
-                        }
-                    }
-                    
-                }
-                if !is_found {
-                    is_new_coverage = true;
-                }
-            }
-
-
-           
-            // reset the new edges so it can be triggered again
-            // println!("Original edges count {} New edges count {}", edges.count, new_edges.count);
-            // check if original edges are subset of new edges
-            let mut found_edges = Vec::new();
-            for i in 0..edges.count {
-                let original_edge = unsafe { *edges.edge_indices.add(i as usize) };
-                for j in 0..new_edges.count {
-                    if unsafe { *new_edges.edge_indices.add(j as usize) } == original_edge {
-                        // crate::cov_clear_edge_data(worker_id, original_edge);
-                        found_edges.push(j);
-                        break;
-                    }
-                    
+            )
+        };
+        let mut new_edges = OwnedEdgeSet::from_native(worker_id);
+        reset_edge_set(worker_id, new_edges.as_edge_set_mut());
+        let new_edges_set = CoverageSet::from_slice(new_edges.as_slice());
 
-                }
-            }
-            // println!("Found {} out of {} edges", found_edges.len(), edges.count);
-            if found_edges.len() as f32 / edges.count as f32 > 0.8 {
-                return (true, is_new_coverage);
+        if get_result_code(result) == ResultCode::Crash {
+            is_new_coverage = true;
+        }
+        if get_result_code(result) == ResultCode::Success {
+            if new_edges_set.len() > edges.len() || !new_edges_set.intersects(&edges) {
+                is_new_coverage = true;
             }
-            // for i in 0..std::cmp::min(edges.count, new_edges.count) {
-            //     println!("Original edge {} New edge {}", unsafe { *edges.edge_indices.add(i as usize) }, unsafe { *new_edges.edge_indices.add(i as usize) });
-            // }
         }
 
+        // check if original edges are (mostly) a subset of new edges
+        if edges.contains_ratio(&new_edges_set) > 0.8 {
+            return (true, is_new_coverage);
+        }
     }
     (false, is_new_coverage)
+}
+
+/// Coverage-preserving minimization of `js_code`: runs `ddmin` (see
+/// `crate::minimize`) over its statements, using
+/// `maintain_coverage_with_mutated_edges` against `edges` as the oracle for
+/// "does this reduced candidate still cover what the original did". Shared
+/// by `Master::check_new_ast_files` and the worker's keep-path so both
+/// shrink entries before saving/broadcasting them.
+pub fn minimize(js_code: &str, worker_id: usize, edges: &EdgeSet) -> String {
+    crate::minimize::ddmin_js_code(js_code, |candidate_statements| {
+        if candidate_statements.is_empty() {
+            return false;
+        }
+        let candidate = candidate_statements.join(";");
+        maintain_coverage_with_mutated_edges(&candidate, worker_id, edges).0
+    })
 }
\ No newline at end of file
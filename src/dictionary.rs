@@ -0,0 +1,36 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A user-supplied set of JS tokens/snippets (identifiers, API names, magic
+/// constants like `Array.prototype`, `-0`, `2**53`) loaded from a
+/// newline-delimited file via `--dictionary`. Feeds `passes::TokenSplicePass`
+/// so builtin names the IPC generator rarely produces on its own still get
+/// exercised, mirroring AFL/LibAFL token mutations.
+#[derive(Clone, Default)]
+pub struct TokenDictionary {
+    tokens: Vec<String>,
+}
+
+impl TokenDictionary {
+    /// Blank lines and lines starting with `#` are ignored, so the same file
+    /// can double as its own documentation.
+    pub fn load_from_file(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let tokens = contents
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.to_string())
+            .collect();
+        Ok(TokenDictionary { tokens })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+}
@@ -0,0 +1,121 @@
+use crate::coverage::{get_result_code_for_profile, reprl_fetch_stdout, ResultCode};
+use std::ffi::CStr;
+
+/// One engine's outcome for a single differential-testing run: its
+/// classified `ResultCode` plus whatever it wrote to stdout, already pulled
+/// across the FFI boundary so callers never touch the raw pointer.
+#[derive(Debug, Clone)]
+pub struct EngineOutcome {
+    pub worker_id: usize,
+    pub profile: String,
+    pub result_code: ResultCode,
+    pub stdout: String,
+}
+
+/// Result of running one test case across several engines: the per-engine
+/// outcomes plus whether they disagree on success/crash classification, or
+/// (among the engines that succeeded) on normalized stdout. `diverges` is
+/// what turns a crash-only harness into a correctness-bug finder — a case
+/// every engine runs to completion but which prints different results is
+/// never reported by crash detection alone.
+#[derive(Debug)]
+pub struct DifferentialResult {
+    pub outcomes: Vec<EngineOutcome>,
+    pub diverges: bool,
+}
+
+/// Strips the variation that makes stdout comparisons noisy across engines
+/// without being a real behavioral difference: leading/trailing whitespace
+/// and `0x`-prefixed addresses (heap/object pointers that differ run to run
+/// even on the same engine, e.g. from default `Object`/`Function` printing).
+fn normalize_stdout(raw: &str) -> String {
+    let chars: Vec<char> = raw.chars().collect();
+    let mut normalized = String::with_capacity(raw.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '0' && i + 1 < chars.len() && (chars[i + 1] == 'x' || chars[i + 1] == 'X') {
+            let mut end = i + 2;
+            while end < chars.len() && chars[end].is_ascii_hexdigit() {
+                end += 1;
+            }
+            if end > i + 2 {
+                normalized.push_str("0xADDR");
+                i = end;
+                continue;
+            }
+        }
+        normalized.push(chars[i]);
+        i += 1;
+    }
+    normalized.trim().to_string()
+}
+
+fn fetch_stdout(worker_id: usize) -> String {
+    unsafe {
+        let ptr = reprl_fetch_stdout(worker_id as i32);
+        if ptr.is_null() {
+            return String::new();
+        }
+        CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+}
+
+fn result_codes_diverge(outcomes: &[EngineOutcome]) -> bool {
+    let mut codes = outcomes.iter().map(|o| &o.result_code);
+    let Some(first) = codes.next() else {
+        return false;
+    };
+    codes.any(|code| code != first)
+}
+
+fn stdout_diverges(outcomes: &[EngineOutcome]) -> bool {
+    let mut normalized = outcomes
+        .iter()
+        .filter(|o| o.result_code == ResultCode::Success)
+        .map(|o| normalize_stdout(&o.stdout));
+    let Some(first) = normalized.next() else {
+        return false;
+    };
+    normalized.any(|s| s != first)
+}
+
+/// Builds the `EngineOutcome` for the calling worker's own profile from a
+/// raw result it already has (e.g. from the main fuzzing execution), so
+/// `run_differential_against_peers` doesn't need to replay `js_code` against
+/// the worker's own REPRL context just to learn what it already knows.
+pub fn own_outcome(worker_id: usize, profile: &str, raw_result: i32) -> EngineOutcome {
+    EngineOutcome {
+        worker_id,
+        profile: profile.to_string(),
+        result_code: get_result_code_for_profile(raw_result, profile),
+        stdout: fetch_stdout(worker_id),
+    }
+}
+
+/// Runs `js_code` against every `(worker_id, profile)` pair in `peers`, each
+/// presumed to already be an initialized REPRL context for that engine (see
+/// `init_reprl_safe`), folds in `own` (the calling worker's own outcome) and
+/// flags divergence across all of them.
+pub fn run_differential_against_peers(
+    own: EngineOutcome,
+    js_code: &str,
+    peers: &[(usize, String)],
+) -> DifferentialResult {
+    let code = format!("{}\0", js_code);
+    let mut outcomes = Vec::with_capacity(peers.len() + 1);
+    outcomes.push(own);
+    for (worker_id, profile) in peers {
+        let raw_result = unsafe {
+            crate::execute_script(code.as_ptr() as *mut i8, crate::MAX_TIMEOUT, 0, *worker_id as i32)
+        };
+        outcomes.push(EngineOutcome {
+            worker_id: *worker_id,
+            profile: profile.clone(),
+            result_code: get_result_code_for_profile(raw_result, profile),
+            stdout: fetch_stdout(*worker_id),
+        });
+    }
+
+    let diverges = result_codes_diverge(&outcomes) || stdout_diverges(&outcomes);
+    DifferentialResult { outcomes, diverges }
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use crate::coverage::ResultCode;
+
+/// Expected classification for one REPRL sanity-check script, independent of
+/// `crate::coverage::ResultCode` so profiles loaded from JSON don't need to
+/// know about that type's derives.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ReprlExpectedResult {
+    Success,
+    Timeout,
+    Error,
+    Crash,
+}
+
+impl ReprlExpectedResult {
+    fn matches(self, code: &ResultCode) -> bool {
+        matches!(
+            (self, code),
+            (ReprlExpectedResult::Success, ResultCode::Success)
+                | (ReprlExpectedResult::Timeout, ResultCode::Timeout)
+                | (ReprlExpectedResult::Error, ResultCode::Error)
+                | (ReprlExpectedResult::Crash, ResultCode::Crash)
+        )
+    }
+}
+
+/// One REPRL sanity-check step: run `script` and expect `expected` back.
+/// A sequence of these replaces the hand-duplicated assertions that used to
+/// live in `v8_reprl_check`/`gecko_reprl_check`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReprlCheckStep {
+    pub script: String,
+    pub expected: ReprlExpectedResult,
+    #[serde(default = "default_check_timeout_ms")]
+    pub timeout_ms: i32,
+}
+
+fn default_check_timeout_ms() -> i32 {
+    1000
+}
+
+fn default_timeout_code() -> i32 {
+    65536
+}
+
+/// Everything `get_result_code` and the REPRL sanity checks used to
+/// hardcode per engine: which exit/signal codes mean a crash, the timeout
+/// sentinel code, and the REPRL sanity-check script sequence. Adding a new
+/// engine (SpiderMonkey shell variants, Hermes, QuickJS, ChakraCore, ...) is
+/// registering one of these instead of editing `get_result_code`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineProfile {
+    pub name: String,
+    pub crash_codes: Vec<i32>,
+    #[serde(default = "default_timeout_code")]
+    pub timeout_code: i32,
+    #[serde(default)]
+    pub reprl_checks: Vec<ReprlCheckStep>,
+}
+
+impl EngineProfile {
+    pub fn classify(&self, result_code: i32) -> ResultCode {
+        if result_code == 0 {
+            return ResultCode::Success;
+        }
+        if result_code == self.timeout_code {
+            return ResultCode::Timeout;
+        }
+        if self.crash_codes.contains(&result_code) {
+            return ResultCode::Crash;
+        }
+        ResultCode::Error
+    }
+}
+
+fn v8_builtin() -> EngineProfile {
+    EngineProfile {
+        name: "v8".to_string(),
+        crash_codes: vec![5, 6, 11],
+        timeout_code: default_timeout_code(),
+        reprl_checks: default_v8_and_gecko_checks(),
+    }
+}
+
+fn gecko_builtin() -> EngineProfile {
+    EngineProfile {
+        name: "gecko".to_string(),
+        crash_codes: vec![256],
+        timeout_code: default_timeout_code(),
+        reprl_checks: default_v8_and_gecko_checks(),
+    }
+}
+
+/// `v8_reprl_check` and `gecko_reprl_check` ran the exact same sequence;
+/// shared here instead of duplicated per profile.
+fn default_v8_and_gecko_checks() -> Vec<ReprlCheckStep> {
+    vec![
+        ReprlCheckStep { script: "var x = 1;".to_string(), expected: ReprlExpectedResult::Success, timeout_ms: 100 },
+        ReprlCheckStep { script: "while(true){}".to_string(), expected: ReprlExpectedResult::Timeout, timeout_ms: 100 },
+        ReprlCheckStep { script: "var x =".to_string(), expected: ReprlExpectedResult::Error, timeout_ms: 1000 },
+        ReprlCheckStep {
+            script: "fuzzilli('FUZZILLI_CRASH', 0);".to_string(),
+            expected: ReprlExpectedResult::Crash,
+            timeout_ms: 1000,
+        },
+        ReprlCheckStep {
+            script: "fuzzilli('FUZZILLI_CRASH', 1);".to_string(),
+            expected: ReprlExpectedResult::Crash,
+            timeout_ms: 1000,
+        },
+        ReprlCheckStep {
+            script: "fuzzilli('FUZZILLI_CRASH', 2);".to_string(),
+            expected: ReprlExpectedResult::Crash,
+            timeout_ms: 1000,
+        },
+    ]
+}
+
+fn jsc_builtin() -> EngineProfile {
+    EngineProfile {
+        name: "jsc".to_string(),
+        crash_codes: vec![256, 6, 11],
+        timeout_code: default_timeout_code(),
+        reprl_checks: Vec::new(),
+    }
+}
+
+/// Holds every registered `EngineProfile`, keyed by name, so `get_result_code`
+/// and the REPRL sanity checks dispatch through whichever profile is
+/// selected instead of a hardcoded match ladder.
+pub struct ProfileRegistry {
+    profiles: HashMap<String, EngineProfile>,
+}
+
+impl ProfileRegistry {
+    /// The profiles this harness has always shipped with, preserved exactly
+    /// so existing `PROFILE=v8`/`gecko`/`jsc` behavior doesn't change.
+    fn with_builtins() -> Self {
+        let mut registry = ProfileRegistry { profiles: HashMap::new() };
+        registry.register(v8_builtin());
+        registry.register(gecko_builtin());
+        registry.register(jsc_builtin());
+        registry
+    }
+
+    pub fn register(&mut self, profile: EngineProfile) {
+        self.profiles.insert(profile.name.clone(), profile);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EngineProfile> {
+        self.profiles.get(name)
+    }
+
+    /// Loads profiles from a JSON file (a top-level array of
+    /// `EngineProfile`s) and merges them in, overriding any built-in of the
+    /// same name. Lets a user add engines without recompiling.
+    pub fn load_from_file(&mut self, path: &Path) -> io::Result<()> {
+        let contents = fs::read_to_string(path)?;
+        let profiles: Vec<EngineProfile> =
+            serde_json::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        for profile in profiles {
+            self.register(profile);
+        }
+        Ok(())
+    }
+
+    /// Classifies `result_code` under the named profile, falling back to the
+    /// `v8` profile if the name isn't registered rather than silently
+    /// treating every result as `Error`.
+    pub fn classify(&self, profile_name: &str, result_code: i32) -> ResultCode {
+        match self.profiles.get(profile_name).or_else(|| self.profiles.get("v8")) {
+            Some(profile) => profile.classify(result_code),
+            None => ResultCode::Error,
+        }
+    }
+}
+
+fn state() -> &'static Mutex<ProfileRegistry> {
+    static STATE: OnceLock<Mutex<ProfileRegistry>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(ProfileRegistry::with_builtins()))
+}
+
+/// Merges additional/overriding profiles from `path` into the process-wide
+/// registry. Call once at startup, before any worker is spawned.
+pub fn load_registry_from_file(path: &Path) -> io::Result<()> {
+    state().lock().unwrap().load_from_file(path)
+}
+
+pub fn register(profile: EngineProfile) {
+    state().lock().unwrap().register(profile);
+}
+
+/// Classifies `result_code` under the named profile via the process-wide
+/// registry; used by `crate::coverage::get_result_code_for_profile`.
+pub fn classify(profile_name: &str, result_code: i32) -> ResultCode {
+    state().lock().unwrap().classify(profile_name, result_code)
+}
+
+/// Runs the named profile's REPRL sanity-check sequence against `worker_id`,
+/// asserting each script's result matches what the profile expects. Replaces
+/// the near-identical `v8_reprl_check`/`gecko_reprl_check` functions with one
+/// dispatch driven by the selected profile's `reprl_checks`.
+pub fn run_reprl_checks(profile_name: &str, worker_id: i32) {
+    let checks = match state().lock().unwrap().get(profile_name) {
+        Some(profile) => profile.reprl_checks.clone(),
+        None => return,
+    };
+    for check in checks {
+        let script = format!("{}\x00", check.script);
+        let result = unsafe { crate::execute_script(script.as_ptr() as *mut i8, check.timeout_ms, 0, worker_id) };
+        let result_code = classify(profile_name, result);
+        println!("{} ({:?} expected): {:?}", check.script, check.expected, result_code);
+        assert!(check.expected.matches(&result_code));
+    }
+}
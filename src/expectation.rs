@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::Deserialize;
+
+/// A generated test case's expected behavior, parsed out of a leading
+/// `//= { "exit": 0, "stdout": "regex...", "stderr": "regex..." }` comment
+/// on the first line of its `code`. Keyed by file descriptor (1 = stdout,
+/// 2 = stderr), which `check` looks up by `STDOUT_FD`/`STDERR_FD`; a stream absent from the
+/// header means "don't care" rather than "expect empty output". Patterns
+/// are matched strictly as regexes -- a literal `.` or `(` in expected
+/// output must be escaped by whoever writes the header.
+#[derive(Debug, Clone, Default)]
+pub struct Expectation {
+    pub exit: Option<i32>,
+    pub streams: HashMap<i32, Regex>,
+}
+
+pub const STDOUT_FD: i32 = 1;
+pub const STDERR_FD: i32 = 2;
+
+#[derive(Deserialize)]
+struct RawExpectation {
+    exit: Option<i32>,
+    stdout: Option<String>,
+    stderr: Option<String>,
+}
+
+const HEADER_PREFIX: &str = "//= ";
+
+/// Parses the `//= {...}` header off the first line of `code`. Returns
+/// `None` for a case with no header (most of them) or a header that fails
+/// to parse as JSON or compile as a set of regexes -- not a hard error,
+/// since an unparseable header just means nothing gets asserted.
+pub fn parse_expectation(code: &str) -> Option<Expectation> {
+    let first_line = code.lines().next()?;
+    let json = first_line.strip_prefix(HEADER_PREFIX)?.trim();
+    let raw: RawExpectation = serde_json::from_str(json).ok()?;
+
+    let mut streams = HashMap::new();
+    if let Some(pattern) = raw.stdout {
+        streams.insert(STDOUT_FD, Regex::new(&pattern).ok()?);
+    }
+    if let Some(pattern) = raw.stderr {
+        streams.insert(STDERR_FD, Regex::new(&pattern).ok()?);
+    }
+    Some(Expectation { exit: raw.exit, streams })
+}
+
+/// One assertion that didn't hold, surfaced as an expected-vs-actual diff
+/// rather than just a pass/fail bit.
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: expected {:?}, got {:?}", self.field, self.expected, self.actual)
+    }
+}
+
+/// Checks a run's exit code/stdout/stderr against `expectation`, returning
+/// every mismatch found. An empty result means the case satisfied every
+/// assertion its header carried.
+pub fn check(expectation: &Expectation, exit_code: i32, stdout: &str, stderr: &str) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+
+    if let Some(expected_exit) = expectation.exit {
+        if expected_exit != exit_code {
+            mismatches.push(Mismatch {
+                field: "exit".to_string(),
+                expected: expected_exit.to_string(),
+                actual: exit_code.to_string(),
+            });
+        }
+    }
+    if let Some(pattern) = expectation.streams.get(&STDOUT_FD) {
+        if !pattern.is_match(stdout) {
+            mismatches.push(Mismatch {
+                field: "stdout".to_string(),
+                expected: pattern.as_str().to_string(),
+                actual: stdout.to_string(),
+            });
+        }
+    }
+    if let Some(pattern) = expectation.streams.get(&STDERR_FD) {
+        if !pattern.is_match(stderr) {
+            mismatches.push(Mismatch {
+                field: "stderr".to_string(),
+                expected: pattern.as_str().to_string(),
+                actual: stderr.to_string(),
+            });
+        }
+    }
+
+    mismatches
+}
@@ -0,0 +1,243 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use dfuzz::PythonWorker;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use crate::generator_client::{GeneratorClient, TestCase};
+use crate::reprl::Reprl;
+
+/// When to give up: either bound can be set independently, and the loop
+/// stops on whichever it hits first. `None` on both means "run forever" --
+/// callers are expected to set at least one in practice.
+pub struct FeedbackLoopConfig {
+    pub worker_id: usize,
+    pub output_dir: PathBuf,
+    pub timeout: Duration,
+    pub max_iterations: Option<u64>,
+    pub max_duration: Option<Duration>,
+    /// Chance (0.0-1.0) each iteration mutates an existing corpus entry via
+    /// the js_fuzzer Python mutator instead of requesting fresh cases from
+    /// the TS generator. Ignored (treated as fresh-only) until the corpus
+    /// has at least one entry to mutate.
+    pub mutate_ratio: f64,
+    pub min_statements: u32,
+    pub max_statements: u32,
+    pub seed: u64,
+}
+
+impl Default for FeedbackLoopConfig {
+    fn default() -> Self {
+        Self {
+            worker_id: 0,
+            output_dir: PathBuf::from("."),
+            timeout: Duration::from_millis(1000),
+            max_iterations: None,
+            max_duration: None,
+            mutate_ratio: 0.5,
+            min_statements: 5,
+            max_statements: 20,
+            seed: 0,
+        }
+    }
+}
+
+/// Point-in-time counters for a running (or just-finished) `FeedbackLoop`.
+#[derive(Debug, Clone, Default)]
+pub struct FeedbackMetrics {
+    pub total_edges: usize,
+    pub corpus_size: usize,
+    pub iterations: u64,
+    pub execs_per_sec: f64,
+    pub crashes_found: u64,
+}
+
+/// Coverage-guided loop: each iteration sources a test case (fresh from
+/// `GeneratorClient`, or an existing corpus entry mutated through the
+/// js_fuzzer Python module via `PythonWorker`), runs it through a `Reprl`
+/// child, and ORs the edges it hit into a global coverage set. A case only
+/// joins the in-memory corpus -- and gets persisted to `output_dir` -- if it
+/// set at least one bit that set didn't already have; everything else is
+/// discarded once it's been scored. Crashing or timing-out inputs are always
+/// saved to `output_dir/crashes`, kept or not, so they can be replayed
+/// through `crate::runner` later.
+pub struct FeedbackLoop {
+    reprl: Reprl,
+    generator: GeneratorClient,
+    python: PythonWorker,
+    rng: SmallRng,
+    config: FeedbackLoopConfig,
+    edges: HashSet<u32>,
+    corpus: Vec<TestCase>,
+    crashes_found: u64,
+    iterations: u64,
+    start: Instant,
+}
+
+impl FeedbackLoop {
+    pub fn new(config: FeedbackLoopConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        fs::create_dir_all(config.output_dir.join("corpus"))?;
+        fs::create_dir_all(config.output_dir.join("crashes"))?;
+
+        Ok(Self {
+            reprl: Reprl::spawn(config.worker_id),
+            generator: GeneratorClient::new()?,
+            python: PythonWorker::new(),
+            rng: SmallRng::seed_from_u64(config.seed),
+            corpus: Vec::new(),
+            edges: HashSet::new(),
+            crashes_found: 0,
+            iterations: 0,
+            start: Instant::now(),
+            config,
+        })
+    }
+
+    fn budget_exhausted(&self) -> bool {
+        if let Some(max_iterations) = self.config.max_iterations {
+            if self.iterations >= max_iterations {
+                return true;
+            }
+        }
+        if let Some(max_duration) = self.config.max_duration {
+            if self.start.elapsed() >= max_duration {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Sources one script: fresh from the TS generator, or a mutation of a
+    /// random existing corpus entry via js_fuzzer's `mutate`. Falls back to
+    /// the unmutated entry (or `None`) if either side errors, rather than
+    /// stalling the whole loop on one bad request.
+    fn next_case(&mut self) -> Option<String> {
+        let should_mutate = !self.corpus.is_empty() && self.rng.gen_bool(self.config.mutate_ratio);
+
+        if should_mutate {
+            let index = self.rng.gen_range(0..self.corpus.len());
+            let base = self.corpus[index].code.clone().unwrap_or_default();
+            let mutated = futures::executor::block_on(
+                self.python.call_python_function("js_fuzzer", "mutate", vec![base.clone()]),
+            );
+            return Some(match mutated {
+                Ok(value) => value.as_str().map(|s| s.to_string()).unwrap_or(base),
+                Err(e) => {
+                    eprintln!("[FEEDBACK] js_fuzzer mutate failed, reusing base case: {}", e);
+                    base
+                }
+            });
+        }
+
+        match self.generator.generate_test_cases(1, self.config.min_statements, self.config.max_statements) {
+            Ok(mut cases) => cases.pop().and_then(|case| case.code),
+            Err(e) => {
+                eprintln!("[FEEDBACK] generator request failed: {}", e);
+                None
+            }
+        }
+    }
+
+    fn save_crash(&self, code: &str) {
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let filename = format!("crash_{}_{}.js", self.config.worker_id, timestamp);
+        let path = self.config.output_dir.join("crashes").join(filename);
+        if let Err(e) = fs::write(&path, code) {
+            eprintln!("[FEEDBACK] failed to save crash to {}: {}", path.display(), e);
+        }
+    }
+
+    fn save_corpus_entry(&self, id: u32, code: &str) {
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let filename = format!("corpus_{}_{}_{}.js", id, self.config.worker_id, timestamp);
+        let path = self.config.output_dir.join("corpus").join(filename);
+        if let Err(e) = fs::write(&path, code) {
+            eprintln!("[FEEDBACK] failed to persist corpus entry to {}: {}", path.display(), e);
+        }
+    }
+
+    pub fn metrics(&self) -> FeedbackMetrics {
+        let elapsed = self.start.elapsed().as_secs_f64();
+        FeedbackMetrics {
+            total_edges: self.edges.len(),
+            corpus_size: self.corpus.len(),
+            iterations: self.iterations,
+            execs_per_sec: if elapsed > 0.0 { self.iterations as f64 / elapsed } else { 0.0 },
+            crashes_found: self.crashes_found,
+        }
+    }
+
+    /// Runs iterations until `max_iterations`/`max_duration` (whichever is
+    /// set and hit first) ends the loop, then returns the final metrics.
+    pub fn run(&mut self) -> FeedbackMetrics {
+        while !self.budget_exhausted() {
+            let Some(code) = self.next_case() else { continue };
+
+            let result = self.reprl.execute(&code, self.config.timeout);
+            self.iterations += 1;
+
+            if result.crashed() || result.timed_out() {
+                self.crashes_found += 1;
+                self.save_crash(&code);
+            }
+
+            let found_new_edge = admit_new_edges(&mut self.edges, result.edges());
+
+            if found_new_edge {
+                let id = self.corpus.len() as u32;
+                self.save_corpus_entry(id, &code);
+                self.corpus.push(TestCase {
+                    id,
+                    filename: None,
+                    code: Some(code),
+                    state: None,
+                    expectation: None,
+                });
+            }
+        }
+
+        self.metrics()
+    }
+}
+
+/// Folds `hit_edges` into the global coverage set, returning whether any of
+/// them were new -- the condition `FeedbackLoop::run` admits a case into the
+/// corpus on. Factored out of `run` so the admission decision is testable
+/// without a live `Reprl`/generator/python worker.
+fn admit_new_edges(edges: &mut HashSet<u32>, hit_edges: &[u32]) -> bool {
+    let mut found_new_edge = false;
+    for &edge in hit_edges {
+        if edges.insert(edge) {
+            found_new_edge = true;
+        }
+    }
+    found_new_edge
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admit_new_edges_true_on_first_sighting() {
+        let mut edges = HashSet::new();
+        assert!(admit_new_edges(&mut edges, &[1, 2, 3]));
+        assert_eq!(edges, HashSet::from([1, 2, 3]));
+    }
+
+    #[test]
+    fn admit_new_edges_false_once_all_edges_are_known() {
+        let mut edges = HashSet::from([1, 2, 3]);
+        assert!(!admit_new_edges(&mut edges, &[1, 2, 3]));
+    }
+
+    #[test]
+    fn admit_new_edges_true_if_even_one_edge_is_new() {
+        let mut edges = HashSet::from([1, 2]);
+        assert!(admit_new_edges(&mut edges, &[1, 2, 99]));
+        assert!(edges.contains(&99));
+    }
+}
@@ -1,16 +1,9 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::io::{BufRead, BufReader, Write};
-use std::process::{Command, Stdio};
-use std::sync::mpsc;
-use std::thread;
-use std::time::Instant;
+use std::time::Duration;
 
-#[derive(Serialize, Deserialize, Debug)]
-struct Message {
-    msg_type: String,
-    data: Value,
-}
+use crate::expectation::{self, Expectation};
+use crate::ipc_transport::{Frame, Transport};
 
 #[derive(Serialize, Debug)]
 struct GenerateRequest {
@@ -29,96 +22,53 @@ pub struct TestCase {
     pub filename: Option<String>,
     pub code: Option<String>,
     pub state: Option<String>,
+    /// Parsed from a leading `//= {...}` header in `code`, if present; see
+    /// `crate::expectation`. Not part of the wire format -- resolved once
+    /// via `resolve_expectation` right after the case is deserialized.
+    #[serde(skip)]
+    pub expectation: Option<Expectation>,
 }
 
-#[derive(Deserialize, Debug)]
-struct GenerateComplete {
-    #[serde(rename = "totalGenerated")]
-    total_generated: u32,
-    #[serde(rename = "elapsedTime")]
-    elapsed_time: f64,
-    rate: f64,
-    #[serde(rename = "outputDir")]
-    output_dir: Option<String>,
+impl TestCase {
+    /// Parses `code`'s expectation header (if any) into `self.expectation`.
+    /// Call once after deserializing/constructing a `TestCase`, before it's
+    /// handed to the runner.
+    pub fn resolve_expectation(&mut self) {
+        self.expectation = self.code.as_deref().and_then(expectation::parse_expectation);
+    }
 }
 
 pub struct GeneratorClient {
-    stdin: std::process::ChildStdin,
-    rx: mpsc::Receiver<Message>,
-    _child: std::process::Child,
+    transport: Transport,
 }
 
 impl GeneratorClient {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
         // Start the TypeScript generator bridge with unique process identifier
         let unique_id = format!("{}-{:?}", std::process::id(), std::thread::current().id());
-        let mut child = Command::new("node")
-            .arg("rust-ts-ipc/ts-app/dist/generator-simple.js")
-            .env("GENERATOR_ID", unique_id)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null()) // Suppress stderr to avoid cluttering
-            .spawn()?;
-
-        let mut stdin = child.stdin.take().ok_or("Failed to get stdin")?;
-        let stdout = child.stdout.take().ok_or("Failed to get stdout")?;
-
-        // Set up channel for receiving messages
-        let (tx, rx) = mpsc::channel();
-
-        // Spawn thread to read responses
-        thread::spawn(move || {
-            let reader = BufReader::new(stdout);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    if let Ok(msg) = serde_json::from_str::<Message>(&line) {
-                        tx.send(msg).ok();
-                    }
-                }
-            }
-        });
-
-        // Send initialization message
-        let init_msg = Message {
-            msg_type: "init".to_string(),
-            data: Value::Null,
-        };
-        writeln!(stdin, "{}", serde_json::to_string(&init_msg)?)?;
-        stdin.flush()?;
-
-        // Wait for init response
-        let mut client = GeneratorClient {
-            stdin,
-            rx,
-            _child: child,
-        };
-
-        // Wait for initialization response
-        if let Ok(response) = client.rx.recv() {
-            if response.msg_type != "init_response" {
-                return Err("Failed to initialize generator".into());
-            }
+        let transport = Transport::spawn(
+            "node",
+            &["rust-ts-ipc/ts-app/dist/generator-simple.js"],
+            &[("GENERATOR_ID", unique_id.as_str())],
+        )?;
+
+        let rx = transport.send_request("init", Value::Null)?;
+        let response = futures::executor::block_on(rx).map_err(|_| "generator process exited before responding to init")?;
+        match response {
+            Frame::Response { success, .. } if success => {}
+            _ => return Err("Failed to initialize generator".into()),
         }
 
-        Ok(client)
+        Ok(GeneratorClient { transport })
     }
 
     pub fn generate_test_cases(&mut self, count: u32, min_statements: u32, max_statements: u32) -> Result<Vec<TestCase>, Box<dyn std::error::Error>> {
-        // Send stop message first to ensure clean state
-        let stop_msg = Message {
-            msg_type: "stop".to_string(),
-            data: Value::Null,
-        };
-        writeln!(self.stdin, "{}", serde_json::to_string(&stop_msg)?)?;
-        self.stdin.flush()?;
-        
-        // Wait a brief moment for stop to process
-        std::thread::sleep(std::time::Duration::from_millis(50));
-        
+        let mut events = self.transport.subscribe_events();
+
         // Use unique output directory per process to avoid conflicts
         let worker_id = std::process::id();
         let output_dir = format!("/tmp/rust-generated-{}", worker_id);
-        
+
         let generate_request = GenerateRequest {
             count,
             min_statements: Some(min_statements),
@@ -126,61 +76,49 @@ impl GeneratorClient {
             output_dir: Some(output_dir),
         };
 
-        let generate_msg = Message {
-            msg_type: "generate".to_string(),
-            data: serde_json::to_value(generate_request)?,
-        };
-
-        writeln!(self.stdin, "{}", serde_json::to_string(&generate_msg)?)?;
-        self.stdin.flush()?;
-
-        let mut test_cases = Vec::new();
-
-        // Collect responses with timeout
-        let timeout_duration = std::time::Duration::from_secs(20);
-        let start_time = Instant::now();
+        let response_rx = self.transport.send_request("generate", serde_json::to_value(generate_request)?)?;
 
-        loop {
-            if start_time.elapsed() > timeout_duration {
-                return Err("Generator timeout".into());
-            }
+        futures::executor::block_on(async move {
+            let mut test_cases = Vec::new();
+            let timeout = tokio::time::sleep(Duration::from_secs(20));
+            tokio::pin!(timeout);
+            tokio::pin!(response_rx);
 
-            if let Ok(response) = self.rx.recv_timeout(std::time::Duration::from_millis(100)) {
-                match response.msg_type.as_str() {
-                    "test_case" => {
-                        if let Ok(test_case) = serde_json::from_value::<TestCase>(response.data) {
-                            test_cases.push(test_case);
-                        }
+            loop {
+                tokio::select! {
+                    _ = &mut timeout => {
+                        return Err::<Vec<TestCase>, Box<dyn std::error::Error>>("Generator timeout".into());
                     }
-                    "generate_complete" => {
-                        break;
-                    }
-                    "error" => {
-                        let error_msg = response.data.as_str().unwrap_or("Unknown error");
-                        if error_msg.contains("Generation already in progress") {
-                            // Wait a bit and retry
-                            std::thread::sleep(std::time::Duration::from_millis(100));
-                            continue;
+                    response = &mut response_rx => {
+                        let frame = response.map_err(|_| "generator process exited mid-request")?;
+                        match frame {
+                            Frame::Response { success, body, .. } => {
+                                if !success {
+                                    return Err(format!("Generator error: {:?}", body).into());
+                                }
+                                return Ok(test_cases);
+                            }
+                            _ => {}
                         }
-                        return Err(format!("Generator error: {:?}", response.data).into());
                     }
-                    _ => {
-                        // Ignore progress and other messages
+                    event = events.recv() => {
+                        if let Ok(Frame::Event { event, body, .. }) = event {
+                            if event == "test_case" {
+                                if let Ok(mut test_case) = serde_json::from_value::<TestCase>(body) {
+                                    test_case.resolve_expectation();
+                                    test_cases.push(test_case);
+                                }
+                            }
+                            // progress and other events are observational only
+                        }
                     }
                 }
             }
-        }
-
-        Ok(test_cases)
+        })
     }
 
-    pub fn shutdown(mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let exit_msg = Message {
-            msg_type: "exit".to_string(),
-            data: Value::Null,
-        };
-        writeln!(self.stdin, "{}", serde_json::to_string(&exit_msg)?)?;
-        self.stdin.flush()?;
+    pub fn shutdown(self) -> Result<(), Box<dyn std::error::Error>> {
+        self.transport.send_request("exit", Value::Null)?;
         Ok(())
     }
-} 
\ No newline at end of file
+}
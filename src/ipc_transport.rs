@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tokio::sync::{broadcast, oneshot};
+
+/// One wire frame, modeled on the Debug Adapter Protocol's request/response/
+/// event split: a `Request` we send gets exactly one `Response` back,
+/// correlated via `request_seq` rather than by guessing from `msg_type`
+/// strings and sleeping until something that looks like the answer shows up.
+/// Anything the far side sends that we didn't ask for (progress, test_case)
+/// arrives as an `Event`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Frame {
+    Request {
+        seq: u64,
+        command: String,
+        #[serde(default)]
+        arguments: Value,
+    },
+    Response {
+        seq: u64,
+        request_seq: u64,
+        success: bool,
+        #[serde(default)]
+        body: Value,
+    },
+    Event {
+        seq: u64,
+        event: String,
+        #[serde(default)]
+        body: Value,
+    },
+}
+
+/// Writes one frame as `Content-Length: N\r\n\r\n` followed by its JSON
+/// body, so the reader never has to guess where one message ends and the
+/// next begins (the failure mode newline-delimited JSON has whenever a
+/// payload contains an embedded newline).
+fn write_frame<W: Write>(writer: &mut W, frame: &Frame) -> io::Result<()> {
+    let body = serde_json::to_vec(frame)?;
+    write!(writer, "Content-Length: {}\r\n\r\n", body.len())?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+/// Reads headers up to the blank line, then exactly `Content-Length` bytes
+/// of JSON body. Returns `Ok(None)` on a clean EOF.
+fn read_frame<R: BufRead>(reader: &mut R) -> io::Result<Option<Frame>> {
+    let mut content_length: Option<usize> = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let Some(len) = content_length else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "frame missing Content-Length header"));
+    };
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body).map(Some).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+type PendingMap = Arc<Mutex<HashMap<u64, oneshot::Sender<Frame>>>>;
+
+/// Length-prefixed request/response transport over a child process's
+/// stdin/stdout. Replaces matching on `msg_type` strings plus
+/// `thread::sleep` polling: every request gets a monotonically increasing
+/// `seq`, the reader thread routes each `Response` to the `oneshot` waiting
+/// on its `request_seq`, and unsolicited `Event`s go out over a broadcast
+/// channel that callers can subscribe to.
+pub struct Transport {
+    stdin: Mutex<ChildStdin>,
+    next_seq: AtomicU64,
+    pending: PendingMap,
+    events: broadcast::Sender<Frame>,
+    child: Mutex<Child>,
+}
+
+impl Transport {
+    /// Spawns `program` and starts the reader thread. `args`/`envs` are
+    /// passed through to the child process unchanged.
+    pub fn spawn(program: &str, args: &[&str], envs: &[(&str, &str)]) -> io::Result<Self> {
+        let mut child = Command::new(program)
+            .args(args)
+            .envs(envs.iter().copied())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let stdin = child.stdin.take().ok_or_else(|| io::Error::new(io::ErrorKind::Other, "failed to get child stdin"))?;
+        let stdout = child.stdout.take().ok_or_else(|| io::Error::new(io::ErrorKind::Other, "failed to get child stdout"))?;
+
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let (events_tx, _) = broadcast::channel(1024);
+
+        let reader_pending = Arc::clone(&pending);
+        let reader_events = events_tx.clone();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            loop {
+                match read_frame(&mut reader) {
+                    Ok(Some(frame @ Frame::Response { .. })) => {
+                        let request_seq = match &frame {
+                            Frame::Response { request_seq, .. } => *request_seq,
+                            _ => unreachable!(),
+                        };
+                        if let Some(sender) = reader_pending.lock().unwrap().remove(&request_seq) {
+                            let _ = sender.send(frame);
+                        }
+                    }
+                    Ok(Some(frame @ Frame::Event { .. })) => {
+                        let _ = reader_events.send(frame);
+                    }
+                    Ok(Some(Frame::Request { .. })) => {
+                        // This transport only drives requests in one direction; a
+                        // Request frame from the far side has no handler here.
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        eprintln!("[IPC] transport read error: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            stdin: Mutex::new(stdin),
+            next_seq: AtomicU64::new(1),
+            pending,
+            events: events_tx,
+            child: Mutex::new(child),
+        })
+    }
+
+    /// Sends `command`/`arguments` as a new `Request` and returns a receiver
+    /// for the matching `Response`, keyed by the `seq` this call assigns.
+    pub fn send_request(&self, command: &str, arguments: Value) -> io::Result<oneshot::Receiver<Frame>> {
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(seq, tx);
+        let frame = Frame::Request { seq, command: command.to_string(), arguments };
+        if let Err(e) = write_frame(&mut *self.stdin.lock().unwrap(), &frame) {
+            self.pending.lock().unwrap().remove(&seq);
+            return Err(e);
+        }
+        Ok(rx)
+    }
+
+    /// Subscribes to unsolicited `Event` frames (progress, test_case, ...)
+    /// emitted between now and whenever the receiver is dropped.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<Frame> {
+        self.events.subscribe()
+    }
+
+    pub fn kill(&self) {
+        let _ = self.child.lock().unwrap().kill();
+    }
+}
+
+impl Drop for Transport {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
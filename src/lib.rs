@@ -1,12 +1,13 @@
 use anyhow::{anyhow, Result};
-// use pyo3::prelude::*;
-// use pyo3::types::{PyDict, PyList, PyTuple};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyTuple};
 use std::collections::HashMap;
 use std::thread;
 use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::sync::oneshot;
 use serde::{Deserialize, Serialize};
 use serde_json;
+use serde_json::Value;
 
 // Message types for our channel
 enum PythonRequest {
@@ -14,7 +15,7 @@ enum PythonRequest {
         module_name: String,
         function_name: String,
         args: Vec<String>,
-        response_tx: oneshot::Sender<Result<HashMap<String, String>>>,
+        response_tx: oneshot::Sender<Result<Value>>,
     },
     Shutdown,
 }
@@ -35,26 +36,26 @@ impl PythonWorker {
     // Create a new Python worker
     pub fn new() -> Self {
         let (request_tx, request_rx) = mpsc::channel(100);
-        
+
         // Spawn a thread that will keep the Python interpreter alive
         thread::spawn(move || {
             if let Err(e) = run_python_worker(request_rx) {
                 eprintln!("Python worker error: {}", e);
             }
         });
-        
+
         Self { request_tx }
     }
-    
+
     // Call a Python function with the given arguments
     pub async fn call_python_function(
-        &self, 
+        &self,
         module_name: &str,
-        function_name: &str, 
+        function_name: &str,
         args: Vec<String>
-    ) -> Result<HashMap<String, String>> {
+    ) -> Result<Value> {
         let (response_tx, response_rx) = oneshot::channel();
-        
+
         self.request_tx
             .send(PythonRequest::Call {
                 module_name: module_name.to_string(),
@@ -64,10 +65,10 @@ impl PythonWorker {
             })
             .await
             .map_err(|_| anyhow!("Failed to send request to Python worker"))?;
-            
+
         response_rx.await.map_err(|_| anyhow!("Python worker was dropped"))?
     }
-    
+
     // Shutdown the Python worker
     pub async fn shutdown(&self) -> Result<()> {
         self.request_tx
@@ -78,108 +79,115 @@ impl PythonWorker {
     }
 }
 
-// Convert a Python dictionary to a Rust HashMap
-// fn py_dict_to_hashmap(py: Python, dict: &PyAny) -> Result<HashMap<String, String>> {
-//     let mut result = HashMap::new();
-    
-//     // Check if the object is a dictionary
-//     if let Ok(py_dict) = dict.downcast::<PyDict>() {
-//         for (key, value) in py_dict.iter() {
-//             let key_str = key.extract::<String>()?;
-            
-//             // Handle different value types
-//             let value_str = match value.get_type().name()? {
-//                 "dict" => {
-//                     // For nested dictionaries, convert to JSON string
-//                     let nested_dict = py_dict_to_hashmap(py, value)?;
-//                     serde_json::to_string(&nested_dict)?
-//                 },
-//                 "list" => {
-//                     // For lists, convert to JSON string
-//                     let list: Vec<String> = value.extract()?;
-//                     serde_json::to_string(&list)?
-//                 },
-//                 _ => {
-//                     // For simple types, extract as string
-//                     value.extract::<String>()?
-//                 }
-//             };
-            
-//             result.insert(key_str, value_str);
-//         }
-//     } else {
-//         return Err(anyhow!("Object is not a dictionary"));
-//     }
-    
-//     Ok(result)
-// }
+// Converts an arbitrary Python value into a `serde_json::Value`, walking
+// dicts/lists/tuples structurally instead of flattening everything to a
+// string -- the previous HashMap<String, String> return type lost nesting
+// by JSON-stringifying dict/list values in place.
+fn py_to_json(py: Python, value: &Bound<PyAny>) -> Result<Value> {
+    if value.is_none() {
+        return Ok(Value::Null);
+    }
+    if let Ok(b) = value.extract::<bool>() {
+        return Ok(Value::Bool(b));
+    }
+    if let Ok(i) = value.extract::<i64>() {
+        return Ok(Value::from(i));
+    }
+    if let Ok(f) = value.extract::<f64>() {
+        return Ok(Value::from(f));
+    }
+    if let Ok(s) = value.extract::<String>() {
+        return Ok(Value::String(s));
+    }
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        let mut map = serde_json::Map::new();
+        for (key, val) in dict.iter() {
+            let key_str = key.extract::<String>().map_err(|e| anyhow!("dict key is not a string: {}", e))?;
+            map.insert(key_str, py_to_json(py, &val)?);
+        }
+        return Ok(Value::Object(map));
+    }
+    if let Ok(list) = value.downcast::<PyList>() {
+        let mut items = Vec::with_capacity(list.len());
+        for item in list.iter() {
+            items.push(py_to_json(py, &item)?);
+        }
+        return Ok(Value::Array(items));
+    }
+    if let Ok(tuple) = value.downcast::<PyTuple>() {
+        let mut items = Vec::with_capacity(tuple.len());
+        for item in tuple.iter() {
+            items.push(py_to_json(py, &item)?);
+        }
+        return Ok(Value::Array(items));
+    }
+
+    // Fall back to str() for anything else (e.g. custom objects) rather
+    // than failing the whole call over one unconvertible value.
+    Ok(Value::String(value.str()?.extract::<String>()?))
+}
+
+// Formats a PyErr with its traceback, if one is attached, so a failure in
+// js_fuzzer surfaces with enough context to debug without re-running under
+// a Python REPL.
+fn format_py_err(py: Python, err: PyErr) -> anyhow::Error {
+    let traceback = err
+        .traceback(py)
+        .and_then(|tb| tb.format().ok())
+        .unwrap_or_default();
+    anyhow!("Python error: {}\n{}", err, traceback)
+}
 
 // The function that runs in the worker thread
 fn run_python_worker(mut request_rx: Receiver<PythonRequest>) -> Result<()> {
-    // Initialize the Python interpreter
-    // pyo3::prepare_freethreaded_python();
-    
-    // Python::with_gil(|py| {
-    //     // Import the Python module
-    //     let sys = py.import("sys")?;
-    //     let path = sys.getattr("path")?;
-    //     path.call_method1("append", ("js_fuzzer",))?;
-    //     path.call_method1("append", ("python",))?;
-        
-    //     // Cache for imported modules
-    //     let mut module_cache: HashMap<String, PyObject> = HashMap::new();
-        
-    //     // Process requests until shutdown
-    //     while let Some(request) = futures::executor::block_on(request_rx.recv()) {
-    //         match request {
-    //             PythonRequest::Call { module_name, function_name, args, response_tx } => {
-    //                 let result = Python::with_gil(|py| {
-    //                     // Get or import the module
-    //                     let module = if let Some(module) = module_cache.get(&module_name) {
-    //                         module.clone()
-    //                     } else {
-    //                         match py.import(module_name.as_str()) {
-    //                             Ok(module) => {
-    //                                 let module_obj = module.to_object(py);
-    //                                 module_cache.insert(module_name.clone(), module_obj.clone());
-    //                                 module_obj
-    //                             }
-    //                             Err(e) => {
-    //                                 return Err(anyhow!("Failed to import module '{}': {}", module_name, e));
-    //                             }
-    //                         }
-    //                     };
-                        
-    //                     let module = module.extract::<&PyAny>(py)?;
-                        
-    //                     // Get the function from the module
-    //                     let func = module
-    //                         .getattr(function_name.as_str())
-    //                         .map_err(|_| anyhow!("Function '{}' not found", function_name))?;
-                        
-    //                     // Convert args to Python values
-    //                     let py_args: Vec<PyObject> = args
-    //                         .iter()
-    //                         .map(|arg| arg.to_object(py))
-    //                         .collect();
-                        
-    //                     // Create a tuple of Python arguments
-    //                     let args_tuple = PyTuple::new(py, &py_args);
-                        
-    //                     // Call the function
-    //                     let result = func.call1(args_tuple)?;
-                        
-    //                     // Convert the result to a Rust HashMap
-    //                     py_dict_to_hashmap(py, result)
-    //                 });
-                    
-    //                 let _ = response_tx.send(result);
-    //             }
-    //             PythonRequest::Shutdown => break,
-    //         }
-    //     }
-        
-    //     Ok(())
-    // })
+    // Free-threaded init is idempotent but only needs to happen once for the
+    // lifetime of this worker thread's interpreter.
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| -> Result<()> {
+        let sys = py.import_bound("sys")?;
+        let path = sys.getattr("path")?;
+        path.call_method1("append", ("js_fuzzer",))?;
+        path.call_method1("append", ("python",))?;
+        Ok(())
+    })?;
+
+    // Cache for imported modules, keyed by module name, so repeated calls
+    // into the same js_fuzzer module don't re-import on every request.
+    let mut module_cache: HashMap<String, Py<PyModule>> = HashMap::new();
+
+    while let Some(request) = futures::executor::block_on(request_rx.recv()) {
+        match request {
+            PythonRequest::Call { module_name, function_name, args, response_tx } => {
+                let result = Python::with_gil(|py| -> Result<Value> {
+                    let module = match module_cache.get(&module_name) {
+                        Some(module) => module.clone_ref(py),
+                        None => {
+                            let module = py
+                                .import_bound(module_name.as_str())
+                                .map_err(|e| format_py_err(py, e))?
+                                .unbind();
+                            module_cache.insert(module_name.clone(), module.clone_ref(py));
+                            module
+                        }
+                    };
+                    let module = module.bind(py);
+
+                    let func = module
+                        .getattr(function_name.as_str())
+                        .map_err(|_| anyhow!("Function '{}' not found in module '{}'", function_name, module_name))?;
+
+                    let args_tuple = PyTuple::new_bound(py, &args);
+                    let result = func.call1(args_tuple).map_err(|e| format_py_err(py, e))?;
+
+                    py_to_json(py, &result)
+                });
+
+                let _ = response_tx.send(result);
+            }
+            PythonRequest::Shutdown => break,
+        }
+    }
+
     Ok(())
-} 
\ No newline at end of file
+}
@@ -0,0 +1,111 @@
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Fast structural dead-code elimination over the program IR, run before the
+/// costly coverage-preserving (re-execution based) minimizer. A classic
+/// backward liveness dataflow over the statement list: a binding is live
+/// coming out of a statement if something after it reads that binding; a
+/// statement that defines a binding which isn't live, and that has no other
+/// side effects, is a dead store and gets dropped.
+///
+/// Operates on the node shape the generator emits: `{"type": ..., "binds":
+/// Option<String>, "reads": [String], "children": [Node], "effectful":
+/// Option<bool>}`. Any node shape we don't recognize is treated as possibly
+/// effectful and is never dropped, and statement order is preserved for
+/// every survivor.
+pub fn prune_dead_code(program_ir: &str) -> String {
+    let Ok(mut root) = serde_json::from_str::<Value>(program_ir) else {
+        return program_ir.to_string();
+    };
+    if let Some(children) = root.get("children").and_then(|c| c.as_array()).cloned() {
+        let (pruned, _live_in) = prune_block(&children, &HashSet::new());
+        if let Some(obj) = root.as_object_mut() {
+            obj.insert("children".to_string(), Value::Array(pruned));
+        }
+    }
+    serde_json::to_string(&root).unwrap_or_else(|_| program_ir.to_string())
+}
+
+/// A binding/definition node is safe to drop only if it is explicitly marked
+/// side-effect free; anything else (including unrecognized node shapes, and
+/// a `VarDecl`/`Assign` with no `effectful` key at all) is conservatively
+/// treated as possibly effectful and never dropped.
+fn is_pure_binding(node: &Value) -> bool {
+    matches!(node.get("type").and_then(|t| t.as_str()), Some("VarDecl") | Some("Assign"))
+        && node.get("effectful").and_then(|e| e.as_bool()) == Some(false)
+}
+
+fn reads_of(node: &Value) -> Vec<String> {
+    node.get("reads")
+        .and_then(|r| r.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+        .unwrap_or_default()
+}
+
+fn binds_of(node: &Value) -> Option<String> {
+    node.get("binds").and_then(|b| b.as_str()).map(|s| s.to_string())
+}
+
+/// Walks one statement list back-to-front, returning the pruned list and the
+/// live-in set (what the caller should treat as live-out of whatever
+/// precedes this block).
+fn prune_block(statements: &[Value], live_out: &HashSet<String>) -> (Vec<Value>, HashSet<String>) {
+    let mut live = live_out.clone();
+    let mut survivors: Vec<Value> = Vec::new();
+
+    for stmt in statements.iter().rev() {
+        if let Some(children) = stmt.get("children").and_then(|c| c.as_array()) {
+            // Control-flow node (if/loop/block): join live-out across every
+            // successor conservatively (union), and for loops iterate the
+            // body to a fixpoint since its live-in can feed its own live-out
+            // on the next iteration.
+            let is_loop = stmt.get("type").and_then(|t| t.as_str()) == Some("Loop");
+            let mut branch_live = live.clone();
+            let mut pruned_children = children.clone();
+            loop {
+                let (next_pruned, next_live) = prune_block(children, &branch_live);
+                pruned_children = next_pruned;
+                let joined: HashSet<String> = next_live.union(&live).cloned().collect();
+                let converged = !is_loop || joined == branch_live;
+                branch_live = joined;
+                if converged {
+                    break;
+                }
+            }
+            for v in reads_of(stmt) {
+                branch_live.insert(v);
+            }
+            live = branch_live;
+
+            let mut stmt = stmt.clone();
+            if let Some(obj) = stmt.as_object_mut() {
+                obj.insert("children".to_string(), Value::Array(pruned_children));
+            }
+            survivors.push(stmt);
+            continue;
+        }
+
+        let binds = binds_of(stmt);
+        let is_dead_store = match &binds {
+            Some(name) => is_pure_binding(stmt) && !live.contains(name),
+            None => false,
+        };
+
+        if is_dead_store {
+            // Statement never executes observably: drop it, and its reads
+            // never become live-in for whatever precedes it.
+            continue;
+        }
+
+        if let Some(name) = &binds {
+            live.remove(name);
+        }
+        for v in reads_of(stmt) {
+            live.insert(v);
+        }
+        survivors.push(stmt.clone());
+    }
+
+    survivors.reverse();
+    (survivors, live)
+}
@@ -1,6 +1,16 @@
 use anyhow::Result;
 use dfuzz::PythonWorker;
 
+/// jemalloc gives `mem_telemetry` real resident/allocated figures via its
+/// introspection API; the system allocator has no equivalent, so campaigns
+/// rely on this to catch memory pressure before the OS OOM-kills a worker.
+#[cfg(not(target_env = "msvc"))]
+use tikv_jemallocator::Jemalloc;
+
+#[cfg(not(target_env = "msvc"))]
+#[global_allocator]
+static GLOBAL: Jemalloc = Jemalloc;
+
 mod coverage;
 use coverage::*;
 use rand::Rng;
@@ -9,14 +19,34 @@ mod corpus;
 use corpus::*;
 mod corpus_aspect;
 use corpus_aspect::*;
+mod ipc_transport;
+mod expectation;
 mod generator_client;
 use generator_client::*;
+mod runner;
+mod reprl;
+mod feedback_loop;
+mod network;
+use network::NetPayload;
+mod passes;
+use passes::{PassContext, PassRegistry, TokenSplicePass};
+mod dictionary;
+use dictionary::TokenDictionary;
+mod metrics;
+mod cmplog;
+mod differential;
+mod engine_profile;
+mod liveness;
+mod validation;
+mod minimize;
+mod bucket_store;
+mod mem_telemetry;
 use std::sync::mpsc::{channel,Sender, Receiver};
 use std::path::PathBuf;
 use std::collections::VecDeque;
 use std::fs::{self, File};
 use std::io::{self, Read, Write};
-use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::time::Duration;
 use std::time::Instant;
 use structopt::StructOpt;
@@ -24,6 +54,7 @@ use std::fs::OpenOptions;
 use chrono::Utc;
 use sanitize_filename::sanitize;
 use ctrlc;
+use core_affinity::CoreId;
 extern "C" {
     fn init(worker_id: i32);
     fn spawn(worker_id: i32);
@@ -60,6 +91,99 @@ struct Opt {
     network_worker: bool,
     #[structopt(long = "port", default_value = "9999")]
     port: u16,
+    /// Comma-separated list of "host:port" peers to push discovered corpus to
+    #[structopt(long = "peers", default_value = "")]
+    peers: String,
+    /// When set, continuously export corpus lineage (parent -> child, by pass) as a DOT graph
+    #[structopt(long = "dump-lineage", parse(from_os_str))]
+    dump_lineage: Option<PathBuf>,
+    /// When set, serve Prometheus/JSON metrics (/metrics, /status, /corpus) on this address,
+    /// e.g. "0.0.0.0:9090"
+    #[structopt(long = "metrics-addr", env = "METRICS_ADDR")]
+    metrics_addr: Option<String>,
+    /// CPU core(s) to pin workers to, e.g. "0-7" or "0,2,4"; "all" (default) uses every
+    /// available logical core, round-robining if there are more workers than cores.
+    #[structopt(long = "cores", default_value = "all")]
+    cores: String,
+    /// Path to a newline-delimited JS token/snippet dictionary (identifiers, API names,
+    /// magic constants like `Array.prototype`, `-0`, `2**53`); blank lines and lines
+    /// starting with `#` are ignored. Feeds the `TokenSplice` mutation pass.
+    #[structopt(long = "dictionary", parse(from_os_str))]
+    dictionary: Option<PathBuf>,
+    /// Comma-separated "worker_id:profile" pairs of already-initialized REPRL
+    /// contexts to differentially test this worker's inputs against, e.g.
+    /// "1:gecko,2:jsc". When set, every input this worker keeps is also run
+    /// on these engines and a mismatched Success/Crash classification or
+    /// differing stdout is reported as a potential correctness bug.
+    #[structopt(long = "diff-profiles", default_value = "")]
+    diff_profiles: String,
+    /// Path to a JSON file (array of engine profile objects: name,
+    /// crash_codes, timeout_code, reprl_checks) registering or overriding
+    /// engine profiles beyond the built-in v8/gecko/jsc, so `PROFILE` can
+    /// select a new engine without recompiling.
+    #[structopt(long = "engine-profiles", parse(from_os_str))]
+    engine_profiles: Option<PathBuf>,
+    /// When set, mirror the in-memory corpus into a memory-mapped,
+    /// bucket-sharded store under this directory so a campaign can scale
+    /// beyond RAM and survive crashes; see `bucket_store::BucketStore`.
+    #[structopt(long = "bucket-store", parse(from_os_str))]
+    bucket_store: Option<PathBuf>,
+    /// When set, encrypt persisted corpus js_code at rest (AES-256-GCM) with
+    /// a key derived from this passphrase via Argon2; requires `--corpus-encryption-salt`.
+    #[structopt(long = "corpus-encryption-passphrase", env = "CORPUS_ENCRYPTION_PASSPHRASE")]
+    corpus_encryption_passphrase: Option<String>,
+    /// Salt for `--corpus-encryption-passphrase`'s Argon2 key derivation; must stay
+    /// stable across runs that should decrypt the same persisted corpus.
+    #[structopt(long = "corpus-encryption-salt", default_value = "rustrunner-corpus")]
+    corpus_encryption_salt: String,
+    /// Resident-memory (bytes, as reported by jemalloc) threshold above which
+    /// should_keep_entry gets stricter and prune_for_memory_pressure starts
+    /// evicting entries. Unset disables memory-pressure-driven retention.
+    #[structopt(long = "corpus-mem-high-water")]
+    corpus_mem_high_water: Option<u64>,
+    /// Resident-memory (bytes) low water mark prune_for_memory_pressure
+    /// evicts down to once the high water mark is crossed; requires
+    /// `--corpus-mem-high-water`.
+    #[structopt(long = "corpus-mem-low-water")]
+    corpus_mem_low_water: Option<u64>,
+    /// Run every `.js` file in this directory through the parallel REPRL
+    /// test runner (see `runner::run_tests`) and exit, instead of fuzzing.
+    #[structopt(long = "run-tests-dir", parse(from_os_str))]
+    run_tests_dir: Option<PathBuf>,
+    /// Seed for the runner's deterministic test-case shuffle; same seed and
+    /// same `--run-tests-dir` contents reproduce the same dispatch order.
+    #[structopt(long = "runner-seed", default_value = "0")]
+    runner_seed: u64,
+    /// Number of REPRL worker threads `--run-tests-dir` spreads cases across.
+    #[structopt(long = "runner-workers", default_value = "4")]
+    runner_workers: usize,
+    /// Output format for `--run-tests-dir`: "pretty", "dot", or "json".
+    #[structopt(long = "runner-reporter", default_value = "pretty")]
+    runner_reporter: String,
+    /// Corpus storage mode: "less-time" (default, keeps every entry's js_code/
+    /// program_ir inline) or "less-memory" (stores a deflate blob per entry,
+    /// decompressing lazily on selection); see `corpus::StorageMode`.
+    #[structopt(long = "storage-mode", default_value = "less-time")]
+    storage_mode: String,
+    /// Run the standalone coverage-guided `feedback_loop::FeedbackLoop` (see
+    /// that module) instead of the normal master/worker fuzzing loop, and
+    /// exit once it stops.
+    #[structopt(long = "feedback-loop")]
+    feedback_loop: bool,
+    /// `--feedback-loop`'s iteration budget; unset runs until `--feedback-duration-secs`
+    /// (or forever, if that's unset too).
+    #[structopt(long = "feedback-iterations")]
+    feedback_iterations: Option<u64>,
+    /// `--feedback-loop`'s wall-clock budget in seconds.
+    #[structopt(long = "feedback-duration-secs")]
+    feedback_duration_secs: Option<u64>,
+    /// Chance (0.0-1.0) each `--feedback-loop` iteration mutates an existing
+    /// corpus entry instead of requesting a fresh case from the generator.
+    #[structopt(long = "feedback-mutate-ratio", default_value = "0.5")]
+    feedback_mutate_ratio: f64,
+    /// RNG seed for `--feedback-loop`'s mutation choices.
+    #[structopt(long = "feedback-seed", default_value = "0")]
+    feedback_seed: u64,
 }
 
 
@@ -68,11 +192,16 @@ enum WorkerMessage {
         program_ir: String,
         js_code: String,
         pass: String,
+        parent_ir_hash: Option<u64>,
     },
     Crash {
         program_ir: String,
         js_code: String,
     },
+    Heartbeat {
+        state: WorkerState,
+        exec_count: u64,
+    },
 }
 
 enum MasterMessage {
@@ -89,6 +218,11 @@ struct Fuzzer {
     to_master: Sender<WorkerMessage>,
     from_master: Receiver<MasterMessage>,
     generator_client: Option<GeneratorClient>,
+    pass_registry: PassRegistry,
+    exec_count: u64, // Total executions this worker has run; reported in heartbeats
+    cmp_table: cmplog::CmpTable, // Operand pairs harvested from CmpEvents, for input-to-state mutation
+    diff_profiles: Vec<(usize, String)>, // Peer (worker_id, profile) pairs to differentially test kept inputs against
+    shutdown: std::sync::Arc<AtomicBool>, // Set by Master::respawn_worker to tell this thread's fuzz() loop to exit instead of running forever as an orphan
 }
 
 
@@ -157,6 +291,7 @@ struct Stats {
     total_crashes: u64,
     total_timeouts: u64,
     total_errors: u64,
+    total_invalid: u64,
     total_coverage: i32,
     corpus_size: i32,
     start_time: Option<Instant>,
@@ -169,6 +304,7 @@ static mut STATS: Stats = Stats {
     total_crashes: 0,
     total_timeouts: 0,
     total_errors: 0,
+    total_invalid: 0,
     total_coverage: 0,
     corpus_size: 0,
     start_time: None,
@@ -176,74 +312,23 @@ static mut STATS: Stats = Stats {
     worker_stats: Vec::new(),
 };
 
-struct Passes {
-    name: String,
-    execution_count: u64,
-    success_count: u64,
-    new_coverage: u64,
-    failure_count: u64,
-    timeout_count: u64,
-    error_count: u64,
-    new_edges: u64,
-    last_cov_time: Option<Instant>,
-}
-impl Passes {
-    fn new(name: String) -> Self {
-        Passes { name, execution_count: 0, success_count: 0, new_coverage: 0, failure_count: 0, timeout_count: 0, error_count: 0, new_edges: 0, last_cov_time: None }
-    }
-    fn update_stats(&mut self, result: i32, new_cov: i32, new_edges: u64) { 
-        self.execution_count += 1;
-        match get_result_code(result) {
-            ResultCode::Success => self.success_count += 1,
-            ResultCode::Crash => self.failure_count += 1,
-            ResultCode::Timeout => self.timeout_count += 1,
-            ResultCode::Error => self.error_count += 1,
-        }
-        if new_cov > 0 {
-            self.new_coverage += 1;
-        }
-        self.new_edges += new_edges;
-        if new_cov > 0 {
-            self.last_cov_time = Some(Instant::now());
-        }
-    }
-}
-static mut PASSES: Vec<Passes> = Vec::new();
-
+/// Renders the cross-worker pass stats `passes::all_stats()` aggregates,
+/// replacing the old global `Vec<Passes>` this table used to scan directly.
 fn print_passes() {
+    let stats = passes::all_stats();
+    let total_edges: u64 = stats.iter().map(|(_, s)| s.new_edges).sum();
 
-    let mut total_edges = 0;
-    unsafe {
-        for pass in &mut PASSES {
-            total_edges += pass.new_edges;
-        }
-    }
     println!("┌────────────────────────────────┬─────────────────┬───────────────┬───────────────┬───────────────┬───────────────┬─────────────┬───────────┬─────────────────┐");
-    println!("│ {:<30} │ {:>15} │ {:>13} │ {:>13} │ {:>13} │ {:>13} │ {:>11} │ {:>9} │ {:>13}   │", 
+    println!("│ {:<30} │ {:>15} │ {:>13} │ {:>13} │ {:>13} │ {:>13} │ {:>11} │ {:>9} │ {:>13}   │",
              "Name", "Execution Count", "Success Count", "New Coverage", "New Edges", "Timeout Count", "Error Count", "Percent", "Last Cov Time");
     println!("├────────────────────────────────┼─────────────────┼───────────────┼───────────────┼───────────────┼───────────────┼─────────────┼───────────┼─────────────────┤");
-    unsafe {
-        for pass in &mut PASSES {
-            println!("│ {:<30} │ {:>15} │ {:>13} │ {:>13} │ {:>13} │ {:>13} │ {:>11} │ {:>9} │ {:>13}   │", 
-                     pass.name, pass.execution_count, format!("{:.2}%", pass.success_count as f64 / pass.execution_count as f64 * 100.0), format!("{:.2}%", pass.new_coverage as f64 / pass.execution_count as f64 * 100.0), 
-                     pass.new_edges, pass.timeout_count, format!("{:.2}%", pass.error_count as f64 / pass.execution_count as f64 * 100.0), format!("{:.2}%", (pass.new_edges as f64 / total_edges as f64) * 100.0), pass.last_cov_time.map(|t| t.elapsed().as_secs()).unwrap_or_default());
-        }
+    for (name, pass) in &stats {
+        println!("│ {:<30} │ {:>15} │ {:>13} │ {:>13} │ {:>13} │ {:>13} │ {:>11} │ {:>9} │ {:>13}   │",
+                 name, pass.execution_count, format!("{:.2}%", pass.success_count as f64 / pass.execution_count as f64 * 100.0), format!("{:.2}%", pass.new_coverage as f64 / pass.execution_count as f64 * 100.0),
+                 pass.new_edges, pass.timeout_count, format!("{:.2}%", pass.error_count as f64 / pass.execution_count as f64 * 100.0), format!("{:.2}%", (pass.new_edges as f64 / total_edges as f64) * 100.0), pass.last_cov_time.map(|t| t.elapsed().as_secs()).unwrap_or_default());
     }
     println!("└────────────────────────────────┴─────────────────┴───────────────┴───────────────┴───────────────┴───────────────┴─────────────┴───────────┴─────────────────┘");
 }
-fn update_passes(name: String, result: i32, new_cov: i32, new_edges: u64) {
-    unsafe {
-        let pass = PASSES.iter_mut().find(|p| p.name == name);
-        if let Some(pass) = pass {
-            pass.update_stats(result, new_cov, new_edges);
-        }
-        else {
-            let mut new_pass = Passes::new(name);
-            new_pass.update_stats(result, new_cov, new_edges);
-            PASSES.push(new_pass);
-        }
-    }
-}
 fn format_duration(duration: Duration) -> String {
     let duration_secs = duration.as_secs_f64();
     
@@ -319,6 +404,7 @@ fn print_stats() {
                 STATS.total_timeouts,
                 STATS.total_timeouts as f64 / STATS.total_executions as f64 * 100.0
             );
+            println!("Rejected (invalid IR, skipped before dispatch): {}", STATS.total_invalid);
 
             if STATS.total_executions > 0 {
                 println!(
@@ -496,13 +582,55 @@ fn init_stats() {
 struct Config {
     corpus_dir: PathBuf,
     output_dir: PathBuf,
+    dump_lineage: Option<PathBuf>,
+    dictionary_path: Option<PathBuf>,
+    diff_profiles: Vec<(usize, String)>,
+    storage_mode: corpus::StorageMode,
+    corpus_encryption_passphrase: Option<String>,
+    corpus_encryption_salt: String,
+    bucket_store: Option<PathBuf>,
+    corpus_mem_high_water: Option<u64>,
+    corpus_mem_low_water: Option<u64>,
 }
+
+/// Parses a `--diff-profiles` spec ("1:gecko,2:jsc") into (worker_id, profile)
+/// pairs, skipping entries that don't parse rather than failing startup over
+/// a malformed differential-testing peer.
+fn parse_diff_profiles(spec: &str) -> Vec<(usize, String)> {
+    spec.split(',')
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let (worker_id, profile) = entry.split_once(':')?;
+            Some((worker_id.trim().parse::<usize>().ok()?, profile.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Parses `--storage-mode` into `corpus::StorageMode`; anything other than
+/// "less-memory" (case-insensitively) falls back to the `LessTime` default
+/// rather than failing startup over a typo'd flag.
+fn parse_storage_mode(spec: &str) -> corpus::StorageMode {
+    match spec.trim().to_lowercase().as_str() {
+        "less-memory" | "less_memory" => corpus::StorageMode::LessMemory,
+        _ => corpus::StorageMode::LessTime,
+    }
+}
+
 impl Config {
     fn new() -> io::Result<Self> {
         let opt = Opt::from_args();
         Ok(Config {
             corpus_dir: opt.corpus_dir,
             output_dir: opt.output_dir,
+            dump_lineage: opt.dump_lineage,
+            dictionary_path: opt.dictionary,
+            diff_profiles: parse_diff_profiles(&opt.diff_profiles),
+            storage_mode: parse_storage_mode(&opt.storage_mode),
+            corpus_encryption_passphrase: opt.corpus_encryption_passphrase,
+            corpus_encryption_salt: opt.corpus_encryption_salt,
+            bucket_store: opt.bucket_store,
+            corpus_mem_high_water: opt.corpus_mem_high_water,
+            corpus_mem_low_water: opt.corpus_mem_low_water,
         })
     }
 }
@@ -539,6 +667,7 @@ impl Fuzzer {
         worker_id: usize,
         to_master: Sender<WorkerMessage>,
         from_master: Receiver<MasterMessage>,
+        shutdown: std::sync::Arc<AtomicBool>,
     ) -> io::Result<Self> {
         // Create output directory if it doesn't exist
         fs::create_dir_all(&opt.output_dir)?;
@@ -546,6 +675,7 @@ impl Fuzzer {
         fs::create_dir_all(opt.output_dir.join("corpus_ir"))?;
         fs::create_dir_all(opt.output_dir.join("corpus_ir_min"))?;
         fs::create_dir_all(opt.output_dir.join("crashes"))?;
+        fs::create_dir_all(opt.output_dir.join("differential"))?;
         println!("Corpus directory: {}", opt.corpus_dir.display());
         init_reprl_safe(worker_id ); 
         if unsafe { worker_id == NUM_WORKERS }{
@@ -572,6 +702,24 @@ impl Fuzzer {
         });
         println!("Total entries: {}", entries.len());
         let mut corpus = CorpusManager::new(worker_id, 10000);
+        corpus.set_storage_mode(opt.storage_mode);
+        if let Some(ref path) = opt.dump_lineage {
+            corpus.set_lineage_path(path.clone());
+        }
+        if let Some(ref passphrase) = opt.corpus_encryption_passphrase {
+            corpus.set_encryption_passphrase(passphrase, opt.corpus_encryption_salt.as_bytes());
+        }
+        if let Err(e) = corpus.set_persist_dir(opt.output_dir.join("corpus_persist")) {
+            eprintln!("Failed to load persisted corpus: {}", e);
+        }
+        if let Some(ref bucket_root) = opt.bucket_store {
+            if let Err(e) = corpus.set_bucket_store_dir(bucket_root.clone(), 4) {
+                eprintln!("Failed to open bucket store: {}", e);
+            }
+        }
+        if let (Some(high_water), Some(low_water)) = (opt.corpus_mem_high_water, opt.corpus_mem_low_water) {
+            corpus.set_memory_pressure_thresholds(high_water, low_water);
+        }
         let mut total_entries = entries.len();
         for entry in entries {
             counter += 1;
@@ -632,6 +780,27 @@ impl Fuzzer {
             None
         };
 
+        let mut pass_registry = PassRegistry::new();
+        if let Some(dictionary_path) = &opt.dictionary_path {
+            match TokenDictionary::load_from_file(dictionary_path) {
+                Ok(dictionary) if !dictionary.is_empty() => {
+                    pass_registry.register(Box::new(TokenSplicePass::new(std::sync::Arc::new(dictionary))));
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!(
+                    "Worker {} failed to load --dictionary {}: {}",
+                    worker_id,
+                    dictionary_path.display(),
+                    e
+                ),
+            }
+        }
+        if let Ok(disabled) = std::env::var("DISABLED_PASSES") {
+            for name in disabled.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+                pass_registry.set_enabled(name, false);
+            }
+        }
+
         Ok(Fuzzer {
             corpus,
             output_dir: opt.output_dir.clone(),
@@ -639,8 +808,24 @@ impl Fuzzer {
             to_master,
             from_master,
             generator_client,
+            pass_registry,
+            exec_count: 0,
+            cmp_table: cmplog::CmpTable::default(),
+            diff_profiles: opt.diff_profiles.clone(),
+            shutdown,
         })
     }
+
+    /// Reports this worker's current state and total execution count to the
+    /// master so it can classify the worker as Active/Idle/Dead. Errors are
+    /// swallowed: if the master's receiver is gone (e.g. mid-respawn) this
+    /// worker is about to be torn down anyway.
+    fn send_heartbeat(&mut self, state: WorkerState) {
+        let _ = self.to_master.send(WorkerMessage::Heartbeat {
+            state,
+            exec_count: self.exec_count,
+        });
+    }
     fn update_entry_result(&mut self, result: i32, new_cov: i32, entry_index: u32) {
         match get_result_code(result) {
             ResultCode::Success => {
@@ -657,8 +842,55 @@ impl Fuzzer {
             }
         }
     }
+    /// Runs every enabled `MutationPass` from `self.pass_registry` against a
+    /// corpus entry, executing each resulting `MutatedProgram` through the
+    /// normal `run_single_input` path so results are attributed uniformly.
+    fn run_registry_passes(&mut self) -> io::Result<()> {
+        let Some(entry) = self.corpus.select_next_input() else {
+            return Ok(());
+        };
+        let mutations = {
+            let mut ctx = PassContext {
+                worker_id: self.worker_id,
+                entry: &entry,
+            };
+            self.pass_registry.run_all(&mut ctx)
+        };
+        for (name, mutated) in mutations {
+            let mut passes = vec![name];
+            self.run_single_input(CorpusEntry::new(mutated.program_ir, mutated.js_code), &mut passes)?;
+        }
+        Ok(())
+    }
+
+    /// RedQueen-style input-to-state mutation: picks a corpus entry, scans
+    /// its `js_code` for numeric literals matching an operand recorded in
+    /// `self.cmp_table`, and substitutes the other side of that comparison
+    /// so the branch it feeds is more likely to flip. Each candidate is run
+    /// through the normal `run_single_input` path, which is what actually
+    /// decides whether it's kept (new coverage via `cov_evaluate_hitcounts`).
+    fn run_input_to_state_pass(&mut self) -> io::Result<()> {
+        if self.cmp_table.is_empty() {
+            return Ok(());
+        }
+        let Some(entry) = self.corpus.select_next_input() else {
+            return Ok(());
+        };
+        let candidates = cmplog::input_to_state_candidates(&self.cmp_table, &entry.js_code);
+        // Cap how many substitutions we try per entry per loop iteration;
+        // a comparison-heavy program can otherwise yield far more candidates
+        // than is worth executing before moving on to other corpus entries.
+        const MAX_CANDIDATES_PER_ENTRY: usize = 8;
+        for js_code in candidates.into_iter().take(MAX_CANDIDATES_PER_ENTRY) {
+            let mut passes = vec!["InputToState".to_string()];
+            self.run_single_input(CorpusEntry::new(entry.program_ir.clone(), js_code), &mut passes)?;
+        }
+        Ok(())
+    }
+
     fn run_single_input(&mut self, entry: CorpusEntry, passes: &mut Vec<String>) -> io::Result<()> {
-      
+        self.exec_count += 1;
+        self.send_heartbeat(WorkerState::Mutating);
 
         update_stats(self.worker_id, 0, 0, WorkerState::Mutating, self.corpus.entries.len() as i32);
         // FUZZ_MODE=1 is for generating new modules base on wasm smith
@@ -667,21 +899,23 @@ impl Fuzzer {
         if entry.js_code.is_empty() {
             return Ok(());
         }
-            let result = unsafe {
-                execute_script(
-                    entry.js_code.clone().as_ptr() as *mut i8,
-                    MAX_TIMEOUT,
-                    0,
-                    self.worker_id as i32,
-                )
-            };
+        if let Err(e) = validation::validate(&entry.program_ir) {
+            unsafe { STATS.total_invalid += 1; }
+            self.log(&format!("Rejected invalid IR before dispatch: {}", e));
+            return Ok(());
+        }
+            // Clears CmpEvents before the run and harvests whatever comparison
+            // operand pairs it produces into self.cmp_table, so a later
+            // run_input_to_state_pass() call has fresh data-flow-derived
+            // substitution candidates to try.
+            let result = cmplog::record_from_execution(&mut self.cmp_table, self.worker_id, &entry.js_code);
             update_stats(self.worker_id, result, 0, WorkerState::Executing, self.corpus.entries.len() as i32);
           
             let elapsed_time = start_time.elapsed();
            
 
             let mut new_edges = EdgeSet::new();
-            let new_cov = unsafe { cov_evaluate(self.worker_id as usize, &mut new_edges) };
+            let new_cov = cov_evaluate_hitcounts(self.worker_id as usize, &mut new_edges) as i32;
             let file_name = format!("{}_{}.js",  self.worker_id,  new_cov);
             
             // Create corpus entry for potential addition
@@ -706,7 +940,7 @@ impl Fuzzer {
             
             if should_keep {
                 for pass in passes.clone() {
-                    update_passes(pass.clone(), result, if has_new_coverage { 1 } else { 0 }, new_cov as u64);
+                    self.pass_registry.record_result(&pass, result, if has_new_coverage { 1 } else { 0 }, new_cov as u64);
                 }
                 
                 if has_new_coverage {
@@ -716,11 +950,24 @@ impl Fuzzer {
                         WorkerState::Executing, 
                         self.corpus.entries.len() as i32);
                     self.update_entry_result(result, new_cov, entry.index);
-                    
+                    let hit_edges: Vec<u64> = (0..new_edges.count)
+                        .map(|i| unsafe { *new_edges.edge_indices.add(i as usize) } as u64)
+                        .collect();
+                    self.corpus.record_entry_coverage(entry.index, &hit_edges, elapsed_time.as_micros() as u64);
+
+                    // Shrink before broadcasting so the distributed corpus
+                    // stays compact; reset/mark the baseline edges the same
+                    // way the master's NewCorpus handler does around its
+                    // own `minimize` call.
+                    reset_edge_set(self.worker_id, &mut new_edges);
+                    let minimized_js = minimize(&entry.js_code, self.worker_id, &new_edges);
+                    mark_edge_set(self.worker_id, &mut new_edges);
+
                     match self.to_master.send(WorkerMessage::NewCorpus {
                         program_ir: entry.program_ir.clone(),
-                        js_code: entry.js_code.clone(),
+                        js_code: minimized_js.clone(),
                         pass: passes[0].clone(),
+                        parent_ir_hash: Some(corpus::hash_str(&entry.program_ir)),
                     }) {
                         Ok(_) => {
                             // self.log("Successfully sent coverage to master");
@@ -728,7 +975,7 @@ impl Fuzzer {
                         Err(e) => {
                             // self.log(&format!("Failed to send coverage to master: {}", e));
                             // self.log("Attempting to save coverage locally...");
-                            match self.save_interesting_input(&entry.js_code, &entry.program_ir, &file_name) 
+                            match self.save_interesting_input(&minimized_js, &entry.program_ir, &file_name)
                             {
                                 Ok(_) => { 
                                     // self.log("Successfully saved coverage locally"); 
@@ -744,11 +991,12 @@ impl Fuzzer {
                     self.log(&format!("Entry kept due to novel bytecode patterns (worker {})", self.worker_id));
                     // we keep the entry in the corpus
                     self.corpus.add_entry(CorpusEntry::new(new_entry.program_ir.clone(), new_entry.js_code.clone()));
-                    update_passes("BytecodeNovelty".to_string(), result, 0, 0);
+                    passes::record_external_result("BytecodeNovelty", result, 0, 0);
                     match self.to_master.send(WorkerMessage::NewCorpus {
                         program_ir: new_entry.program_ir.clone(),
                         js_code: new_entry.js_code.clone(),
                         pass: "BytecodeNovelty".to_string(),
+                        parent_ir_hash: Some(corpus::hash_str(&entry.program_ir)),
                     }) {
                         Ok(_) => {
                             // self.log("Successfully sent bytecode novel entry to master");
@@ -768,11 +1016,11 @@ impl Fuzzer {
                     }
                 }
             } else {
-                for pass in passes {
-                    update_passes(pass.clone(), result, 0, 0);
+                for pass in passes.iter() {
+                    self.pass_registry.record_result(pass, result, 0, 0);
                 }
             }
-              
+
 
            
 
@@ -794,7 +1042,28 @@ impl Fuzzer {
                 }
             }
 
-         
+            // Only worth the per-peer-engine replay cost for inputs we're
+            // actually keeping -- an input we're about to discard isn't worth
+            // differential-testing, and its ResultCode/stdout are already
+            // known from the main execution above, so the worker's own
+            // profile doesn't need a redundant re-execution here either.
+            if should_keep && !self.diff_profiles.is_empty() && get_result_code(result) != ResultCode::Timeout {
+                let own_profile = std::env::var("PROFILE").unwrap_or_else(|_| "v8".to_string());
+                let own = differential::own_outcome(self.worker_id, &own_profile, result);
+                let diff_result = differential::run_differential_against_peers(own, &entry.js_code, &self.diff_profiles);
+                if diff_result.diverges {
+                    self.log(&format!(
+                        "Differential mismatch across engines: {:?}",
+                        diff_result.outcomes.iter().map(|o| (&o.profile, &o.result_code)).collect::<Vec<_>>()
+                    ));
+                    match self.save_differential_result(&entry.js_code, &file_name, &diff_result) {
+                        Ok(_) => self.log("Successfully saved differential mismatch locally"),
+                        Err(e) => self.log(&format!("Failed to save differential mismatch locally: {}", e)),
+                    }
+                }
+            }
+
+
 
         Ok(())
     }
@@ -832,6 +1101,34 @@ impl Fuzzer {
 
         Ok(())
     }
+
+    /// Saves a test case alongside its per-engine outcomes when
+    /// `differential::run_differential` finds disagreement, so it can be
+    /// triaged as a correctness bug rather than just logged.
+    fn save_differential_result(
+        &mut self,
+        test_code: &str,
+        original_file: &str,
+        diff_result: &differential::DifferentialResult,
+    ) -> io::Result<()> {
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let js_filename = format!("{}_{}_{}.js", original_file, self.worker_id, timestamp);
+        let js_file = self.output_dir.join("differential").join(js_filename);
+        let test_code_ = test_code.replace("\x00", "");
+        fs::write(&js_file, test_code_.as_bytes())?;
+
+        let report_filename = format!("{}_{}_{}.txt", original_file, self.worker_id, timestamp);
+        let report_file = self.output_dir.join("differential").join(report_filename);
+        let report = diff_result
+            .outcomes
+            .iter()
+            .map(|o| format!("worker {} ({}): {:?}\n{}", o.worker_id, o.profile, o.result_code, o.stdout))
+            .collect::<Vec<_>>()
+            .join("\n---\n");
+        fs::write(&report_file, report.as_bytes())?;
+
+        Ok(())
+    }
     fn is_master(&self) -> bool {
         unsafe { self.worker_id == NUM_WORKERS }
     }
@@ -888,8 +1185,12 @@ impl Fuzzer {
         // Main fuzzing loop
 
         loop {
+            if self.shutdown.load(Ordering::Relaxed) {
+                self.log("Shutdown signal received; exiting fuzz loop");
+                return Ok(());
+            }
             let mut passes = Vec::new();
-          
+
             // Generate test cases using IPC instead of disk I/O
             if let Some(ref mut generator_client) = self.generator_client {
                 update_stats(self.worker_id, 0, 0, WorkerState::Generating, self.corpus.entries.len() as i32);
@@ -909,8 +1210,11 @@ impl Fuzzer {
                         self.log(&format!("Failed to generate test cases via IPC: {}", e));
                     }
                 }
-            } 
-            
+            }
+
+            self.run_registry_passes()?;
+            self.run_input_to_state_pass()?;
+
             // Check for messages from master
             while let Ok(msg) = self.from_master.try_recv() {
                 match msg {
@@ -956,14 +1260,67 @@ impl Fuzzer {
  
 }
 
+/// Per-worker liveness table entry, fed by `WorkerMessage::Heartbeat`.
+/// `exec_count` and `restarts` are persisted to disk (see
+/// `Master::{load,persist}_worker_health`) so a master restart doesn't lose
+/// a long campaign's totals, even though `last_heartbeat`/`state` are
+/// necessarily reset to "just started" at that point.
+#[derive(Debug, Clone)]
+struct WorkerHealth {
+    last_heartbeat: Instant,
+    state: WorkerState,
+    exec_count: u64,
+    restarts: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WorkerClass {
+    Active,
+    Idle,
+    Dead,
+}
+
 struct Master {
     fuzzer: Fuzzer,
     from_workers: Vec<Receiver<WorkerMessage>>,
     to_workers: Vec<Sender<MasterMessage>>,
     initialized: bool,
+    from_network: Option<std::sync::mpsc::Receiver<NetPayload>>,
+    to_network: Option<Sender<NetPayload>>,
+    config: Config,
+    num_workers: usize,
+    worker_handles: Vec<Option<std::thread::JoinHandle<()>>>,
+    worker_shutdown: Vec<std::sync::Arc<AtomicBool>>, // Signals the currently running thread for each worker_id to exit its fuzz() loop; respawn_worker flips the old one before joining it
+    worker_health: Vec<WorkerHealth>,
+    last_supervision_check: Instant,
+    start_time: Instant,
+    last_metrics_publish: Instant,
+    core_ids: Vec<CoreId>,
 }
 
 impl Master {
+    /// Wires up the distributed corpus sync subsystem described by
+    /// `--network-worker`/`--port`/`--peers`. A no-op when network mode is off.
+    fn enable_network_sync(&mut self, opt: &Opt) {
+        if !opt.network_worker {
+            return;
+        }
+        let peers: Vec<String> = opt
+            .peers
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        let mut feature_bits = 0u32;
+        if std::env::var("BYTECODE_COLLECTOR").unwrap_or_else(|_| "0".to_string()) == "1" {
+            feature_bits |= network::FEATURE_BYTECODE_COLLECTOR;
+        }
+        feature_bits |= network::FEATURE_GENERATOR_CLIENT;
+        let (inbound, outbound) = network::start(opt.port, peers, feature_bits);
+        self.from_network = Some(inbound);
+        self.to_network = Some(outbound);
+    }
+
     async fn new(config: &Config, num_workers: usize) -> io::Result<Self> {
         let mut from_workers = Vec::new();
         let mut to_workers = Vec::new();
@@ -986,25 +1343,360 @@ impl Master {
             num_workers, // Use num_workers as master's ID to avoid conflict
             tx_dummy_worker,
             rx_dummy_master,
+            std::sync::Arc::new(AtomicBool::new(false)),
         ).await?;
         init_reprl_safe(num_workers); 
         
         // Create remote_corpus directory if it doesn't exist
         let remote_corpus_dir = config.output_dir.join("remote_corpus");
         fs::create_dir_all(&remote_corpus_dir)?;
-        
+
         Ok(Master {
             fuzzer,
             from_workers,
             to_workers,
             initialized: false,
+            from_network: None,
+            to_network: None,
+            config: config.clone(),
+            num_workers,
+            worker_handles: (0..num_workers).map(|_| None).collect(),
+            worker_shutdown: (0..num_workers).map(|_| std::sync::Arc::new(AtomicBool::new(false))).collect(),
+            worker_health: Self::load_worker_health(num_workers),
+            last_supervision_check: Instant::now(),
+            start_time: Instant::now(),
+            last_metrics_publish: Instant::now(),
+            core_ids: core_affinity::get_core_ids().unwrap_or_default(),
         })
     }
-    
+
+    /// Parses a `--cores` spec ("all", "0-7", or "0,2,4") into the subset of
+    /// `available` it refers to. Unknown/unparseable entries are dropped;
+    /// falls back to `available` wholesale if nothing in the spec resolved.
+    fn parse_cores_spec(spec: &str, available: &[CoreId]) -> Vec<CoreId> {
+        if spec.trim().eq_ignore_ascii_case("all") {
+            return available.to_vec();
+        }
+        let mut requested_ids = Vec::new();
+        for part in spec.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            if let Some((start, end)) = part.split_once('-') {
+                if let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse::<usize>()) {
+                    requested_ids.extend(start..=end);
+                }
+            } else if let Ok(id) = part.parse::<usize>() {
+                requested_ids.push(id);
+            }
+        }
+        let resolved: Vec<CoreId> = requested_ids
+            .into_iter()
+            .filter_map(|id| available.iter().find(|core| core.id == id).copied())
+            .collect();
+        if resolved.is_empty() {
+            available.to_vec()
+        } else {
+            resolved
+        }
+    }
+
+    /// Resolves the `--cores` spec against the machine's actual logical
+    /// cores; `spawn_worker_thread` round-robins workers across whatever
+    /// this leaves in `self.core_ids`.
+    fn configure_cores(&mut self, opt: &Opt) {
+        let available = core_affinity::get_core_ids().unwrap_or_default();
+        self.core_ids = Self::parse_cores_spec(&opt.cores, &available);
+        if self.core_ids.is_empty() {
+            eprintln!("[cores] no usable CPU cores found; workers will run unpinned");
+        } else {
+            println!("[cores] pinning workers to: {:?}", self.core_ids);
+        }
+    }
+
+    /// Starts the `--metrics-addr`/`METRICS_ADDR` admin HTTP server, if configured.
+    fn enable_metrics_server(&self, opt: &Opt) {
+        let Some(addr) = &opt.metrics_addr else {
+            return;
+        };
+        match addr.parse::<std::net::SocketAddr>() {
+            Ok(addr) => metrics::start(addr),
+            Err(e) => eprintln!("[metrics] invalid --metrics-addr {}: {}", addr, e),
+        }
+    }
+
+    // How often `run`'s main loop republishes the metrics snapshot served by
+    // the admin HTTP endpoints.
+    const METRICS_PUBLISH_INTERVAL: Duration = Duration::from_secs(2);
+
+    /// Builds a fresh `metrics::Snapshot` from corpus/worker/pass state and
+    /// publishes it for the admin HTTP server to serve. Cheap enough to call
+    /// every loop iteration, but throttled anyway since nothing external
+    /// needs sub-second freshness.
+    fn publish_metrics(&mut self) {
+        if self.last_metrics_publish.elapsed() < Self::METRICS_PUBLISH_INTERVAL {
+            return;
+        }
+        self.last_metrics_publish = Instant::now();
+
+        let workers = self
+            .worker_health
+            .iter()
+            .enumerate()
+            .map(|(worker_id, health)| metrics::WorkerSnapshot {
+                worker_id,
+                state: format!("{:?}", health.state),
+                exec_count: health.exec_count,
+                restarts: health.restarts,
+            })
+            .collect();
+
+        let passes = passes::all_stats()
+            .into_iter()
+            .map(|(name, pass)| metrics::PassSnapshot {
+                name,
+                execution_count: pass.execution_count,
+                success_count: pass.success_count,
+                new_coverage: pass.new_coverage,
+                new_edges: pass.new_edges,
+                timeout_count: pass.timeout_count,
+                error_count: pass.error_count,
+            })
+            .collect();
+
+        let corpus_entries = self
+            .fuzzer
+            .corpus
+            .entries
+            .iter()
+            .map(|entry| metrics::CorpusEntrySummary {
+                index: entry.index,
+                js_code_len: entry.js_code.len(),
+                coverage_found: entry.coverage_found,
+                is_favored: entry.is_favored,
+                discovered_by_pass: entry.discovered_by_pass.clone(),
+            })
+            .collect();
+
+        let (total_executions, total_crashes, total_timeouts) =
+            unsafe { (STATS.total_executions, STATS.total_crashes, STATS.total_timeouts) };
+
+        metrics::publish(metrics::Snapshot {
+            corpus_size: self.fuzzer.corpus.entries.len(),
+            edges_covered: self.fuzzer.corpus.edges_covered(),
+            total_executions,
+            total_crashes,
+            total_timeouts,
+            uptime_secs: self.start_time.elapsed().as_secs(),
+            workers,
+            passes,
+            corpus_entries,
+        });
+    }
+
     // Fix the corpus clone method to correctly return the fuzzer's corpus
     fn get_corpus_clone(&self) -> CorpusManager {
         self.fuzzer.corpus.clone()
     }
+
+    // How long a worker can go without a heartbeat before it's considered
+    // Dead and gets respawned.
+    const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+    // How often `run`'s main loop re-checks worker liveness.
+    const SUPERVISION_INTERVAL: Duration = Duration::from_secs(5);
+
+    /// Spawns (or respawns) worker `worker_id`'s thread: fresh channels,
+    /// seeded with a clone of the master's current `CorpusManager`, running
+    /// the same init + `fuzz()` loop the initial spawn in `main` used to run
+    /// inline. A fresh shutdown flag is handed to this thread's `Fuzzer`;
+    /// `respawn_worker` is what actually tells a previous thread to stop,
+    /// by flipping its own flag before calling this.
+    fn spawn_worker_thread(&mut self, worker_id: usize) {
+        let (tx_worker, rx_master) = channel();
+        let (tx_master, rx_worker) = channel();
+        self.from_workers[worker_id] = rx_master;
+        self.to_workers[worker_id] = tx_master;
+
+        let worker_config = self.config.clone();
+        let mut worker_corpus = self.fuzzer.corpus.clone();
+        worker_corpus.worker_id = worker_id;
+        // Round-robin in case there are more workers than pinnable cores.
+        let core_id = (!self.core_ids.is_empty()).then(|| self.core_ids[worker_id % self.core_ids.len()]);
+
+        let shutdown = std::sync::Arc::new(AtomicBool::new(false));
+        self.worker_shutdown[worker_id] = shutdown.clone();
+
+        let handle = std::thread::spawn(move || {
+            if let Some(core_id) = core_id {
+                if !core_affinity::set_for_current(core_id) {
+                    eprintln!("Worker {} failed to pin to core {:?}", worker_id, core_id);
+                }
+            }
+
+            println!("Initializing worker {}", worker_id);
+            init_reprl_safe(worker_id);
+
+            let mut fuzzer = match futures::executor::block_on(Fuzzer::new(
+                &worker_config,
+                worker_id,
+                tx_worker,
+                rx_worker,
+                shutdown,
+            )) {
+                Ok(mut fuzzer) => {
+                    fuzzer.set_corpus(worker_corpus);
+                    fuzzer
+                }
+                Err(e) => {
+                    eprintln!("Worker {} initialization failed: {}", worker_id, e);
+                    return;
+                }
+            };
+
+            println!("Worker {} initialized", worker_id);
+            if let Err(e) = fuzzer.fuzz() {
+                eprintln!("Worker {} exited with error: {}", worker_id, e);
+            }
+        });
+
+        self.worker_handles[worker_id] = Some(handle);
+    }
+
+    /// Spawns every worker's thread; called once by `main` at startup.
+    fn spawn_all_workers(&mut self) {
+        for worker_id in 0..self.num_workers {
+            self.spawn_worker_thread(worker_id);
+        }
+    }
+
+    /// Records a heartbeat from `worker_id`, refreshing its last-seen time
+    /// so `classify_worker` doesn't consider it Dead.
+    fn record_heartbeat(&mut self, worker_id: usize, state: WorkerState, exec_count: u64) {
+        if let Some(health) = self.worker_health.get_mut(worker_id) {
+            health.last_heartbeat = Instant::now();
+            health.state = state;
+            health.exec_count = exec_count;
+        }
+    }
+
+    fn classify_worker(health: &WorkerHealth) -> WorkerClass {
+        if health.last_heartbeat.elapsed() > Self::HEARTBEAT_TIMEOUT {
+            WorkerClass::Dead
+        } else if health.state == WorkerState::Idle || health.state == WorkerState::Waiting {
+            WorkerClass::Idle
+        } else {
+            WorkerClass::Active
+        }
+    }
+
+    /// Tears down and respawns a worker detected as Dead, re-seeding it from
+    /// the master's current corpus. Restart count and prior exec count are
+    /// preserved in `worker_health` rather than reset to zero.
+    ///
+    /// A dropped `JoinHandle` does not stop its thread -- `fuzz()`'s loop
+    /// only does non-blocking `try_recv`s, so disconnecting its channels
+    /// wouldn't make it exit either. Flip its shutdown flag and join it
+    /// first, so the old thread is actually gone before a new one reuses
+    /// `worker_id` (and its REPRL/coverage slot).
+    fn respawn_worker(&mut self, worker_id: usize) {
+        self.worker_shutdown[worker_id].store(true, Ordering::Relaxed);
+        if let Some(old_handle) = self.worker_handles[worker_id].take() {
+            if let Err(e) = old_handle.join() {
+                self.fuzzer.log(&format!("Worker {} thread panicked during respawn: {:?}", worker_id, e));
+            }
+        }
+        self.worker_health[worker_id].restarts += 1;
+        let restarts = self.worker_health[worker_id].restarts;
+        let exec_count = self.worker_health[worker_id].exec_count;
+
+        self.spawn_worker_thread(worker_id);
+
+        self.worker_health[worker_id].last_heartbeat = Instant::now();
+        self.worker_health[worker_id].state = WorkerState::Idle;
+        self.fuzzer.log(&format!(
+            "Worker {} missed its heartbeat for over {:?}; respawned (restart #{}, exec_count {} preserved)",
+            worker_id, Self::HEARTBEAT_TIMEOUT, restarts, exec_count
+        ));
+    }
+
+    /// Throttled liveness sweep: classifies every worker and respawns any
+    /// that are Dead. Run from the main `run` loop alongside message
+    /// draining and `check_new_ast_files`.
+    fn supervise_workers(&mut self) {
+        if self.last_supervision_check.elapsed() < Self::SUPERVISION_INTERVAL {
+            return;
+        }
+        self.last_supervision_check = Instant::now();
+
+        for worker_id in 0..self.num_workers {
+            if Self::classify_worker(&self.worker_health[worker_id]) == WorkerClass::Dead {
+                self.respawn_worker(worker_id);
+            }
+        }
+        self.persist_worker_health();
+    }
+
+    /// Loads persisted per-worker exec/restart counts from a prior master
+    /// run, if any, so a master restart resumes meaningful totals instead of
+    /// starting every worker back at zero.
+    fn load_worker_health(num_workers: usize) -> Vec<WorkerHealth> {
+        let persisted: HashMap<usize, (u64, u32)> = fs::read_to_string("stats/worker_health.json")
+            .ok()
+            .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+            .and_then(|value| value.as_array().cloned())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let worker_id = entry.get("worker_id")?.as_u64()? as usize;
+                        let exec_count = entry.get("exec_count")?.as_u64()?;
+                        let restarts = entry.get("restarts")?.as_u64()? as u32;
+                        Some((worker_id, (exec_count, restarts)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        (0..num_workers)
+            .map(|worker_id| {
+                let (exec_count, restarts) = persisted.get(&worker_id).copied().unwrap_or((0, 0));
+                WorkerHealth {
+                    last_heartbeat: Instant::now(),
+                    state: WorkerState::Idle,
+                    exec_count,
+                    restarts,
+                }
+            })
+            .collect()
+    }
+
+    /// Writes the current per-worker exec/restart counts to disk so the next
+    /// master run can pick them back up; mirrors the `stats/worker_*` JSON
+    /// dumps `CorpusManager::dump_stats_to_json` already produces.
+    fn persist_worker_health(&self) {
+        if let Err(e) = fs::create_dir_all("stats") {
+            self.fuzzer.log(&format!("Failed to create stats directory: {}", e));
+            return;
+        }
+        let entries: Vec<_> = self
+            .worker_health
+            .iter()
+            .enumerate()
+            .map(|(worker_id, health)| {
+                serde_json::json!({
+                    "worker_id": worker_id,
+                    "exec_count": health.exec_count,
+                    "restarts": health.restarts,
+                })
+            })
+            .collect();
+        if let Ok(json) = serde_json::to_string_pretty(&entries) {
+            if let Err(e) = fs::write("stats/worker_health.json", json) {
+                self.fuzzer.log(&format!("Failed to persist worker health: {}", e));
+            }
+        }
+    }
     
     fn check_new_ast_files(&mut self) -> io::Result<()> {
         // Path to the remote_corpus directory
@@ -1081,46 +1773,17 @@ impl Master {
             // If we found new coverage, process it
             if new_cov > 0 {
                 update_stats(unsafe { NUM_WORKERS }, result, new_cov as i32, WorkerState::Generating, self.fuzzer.corpus.entries.len() as i32);
-                
-                // Try to minimize the input
-                let mut minimized_ir_list = Vec::new();
-                let mut minimized_js_list = Vec::new();
-                
-            
-                
-                // Find the smallest minimized version that maintains coverage
-                let mut is_maintained = false;
-                let mut minimized_js_final = String::new();
-                let mut minimized_ir_final = String::new();
-                
-                if !minimized_js_list.is_empty() {
-                    // Sort by length
-                    minimized_js_list.sort_by_key(|js: &String| js.len());
-                    minimized_ir_list.sort_by_key(|ir: &String| ir.len());
-                    
-                    reset_edge_set(self.fuzzer.worker_id as usize, &mut new_edges);
-                    
-                    for (minimized_ir, minimized_js) in minimized_ir_list.iter().zip(minimized_js_list.iter()) {
-                        if minimized_js.is_empty() {
-                            continue;
-                        }
-                        
-                        (is_maintained, _) = maintain_coverage_with_mutated_edges(
-                            &minimized_js, 
-                            self.fuzzer.worker_id as usize, 
-                            &new_edges
-                        );
-                        
-                        if is_maintained {
-                            minimized_js_final = minimized_js.clone();
-                            minimized_ir_final = minimized_ir.clone();
-                            break;
-                        }
-                    }
-                    
-                    mark_edge_set(self.fuzzer.worker_id as usize, &mut new_edges);
-                }
-                
+
+                // Shrink the input to a coverage-equivalent minimal form via
+                // ddmin, using the edges captured above as the oracle.
+                reset_edge_set(self.fuzzer.worker_id as usize, &mut new_edges);
+                let minimized_js_candidate = minimize(&js_code, self.fuzzer.worker_id as usize, &new_edges);
+                mark_edge_set(self.fuzzer.worker_id as usize, &mut new_edges);
+
+                let is_maintained = minimized_js_candidate.len() < js_code.len();
+                let minimized_js_final = minimized_js_candidate;
+                let minimized_ir_final = program_ir.clone();
+
                 // Save and distribute the corpus entry
                 if is_maintained && !minimized_js_final.is_empty() {
                     // Use minimized version
@@ -1191,8 +1854,10 @@ impl Master {
                         program_ir,
                         js_code,
                         pass,
+                        parent_ir_hash,
                     }) => {
                         consecutive_errors = 0;  // Reset error counter on successful message
+                        let parent_index = parent_ir_hash.and_then(|h| self.fuzzer.corpus.find_index_by_ir_hash(h));
                          // remove comment from test_code
                         // Verify new coverage
                         if js_code.is_empty() {
@@ -1218,51 +1883,57 @@ impl Master {
                             .log(&format!("new cov: {} from worker {} ", new_cov, worker_id));
 
                         update_stats(unsafe { NUM_WORKERS }, result, 0 , WorkerState::CoverageCheck, self.fuzzer.corpus.entries.len() as i32);
-                        // let mut mutated_edges = unsafe { extract_testcase_coverage(&js_code, self.fuzzer.worker_id as usize, &mut new_edges) };
-                        // if mutated_edges.count == 0 {
-                        //     self.fuzzer.log(&format!("Discard new cov from worker {} ", worker_id));
-                        // }
+                        // Re-run the candidate a handful of times and only trust
+                        // edges that stabilize under a strict quorum, so run-to-run
+                        // noise (GC, JIT tiering, ...) doesn't get promoted to
+                        // corpus-wide coverage off the strength of a single execution.
+                        let mutated_edges = extract_testcase_coverage(&js_code, self.fuzzer.worker_id as usize, &new_edges);
+                        if mutated_edges.count() == 0 {
+                            self.fuzzer.log(&format!("Discard new cov from worker {} (coverage did not stabilize)", worker_id));
+                            new_cov = 0;
+                        }
                         if new_cov > 0 {
                         // if new_cov > 0 {
                             // let reducer = WasmReducer::new(0, mutated_edges).unwrap();
                             // let (reduced_wasm, new_cov_wasm_modules) = reducer.reduce(&mutated_wasm);
                             // self.fuzzer.log(&format!("Reduced sample size from {} to {}", mutated_wasm.len(), reduced_wasm.len()));
                             // self.fuzzer.log(&format!("New cov wasm modules count: {}", new_cov_wasm_modules.len()));
-                            let minimized_ir = program_ir.clone();
-                            let minimized_js = "".to_string();
-
-                            let mut minimized_ir_list = Vec::new();
-                            let mut minimized_js_list = Vec::new();
+                            // Fast structural pre-pass: strip dead stores/bindings from the
+                            // IR before paying for the costly re-execution based minimizer.
+                            // `prune_dead_code` never executes anything, so its output gets
+                            // the same before-dispatch structural check every other IR does
+                            // (`validation::validate`) before it's trusted as canonical --
+                            // a pruning bug that drops something live fails validation
+                            // (e.g. a dangling read) instead of silently shipping a
+                            // corpus entry whose IR no longer matches its own behavior.
+                            let pruning_candidate = liveness::prune_dead_code(&program_ir);
+                            let minimized_ir = if pruning_candidate.len() != program_ir.len()
+                                && validation::validate(&pruning_candidate).is_err()
+                            {
+                                self.fuzzer.log("Liveness-pruned IR failed validation; keeping original IR");
+                                program_ir.clone()
+                            } else {
+                                pruning_candidate
+                            };
+                            if minimized_ir.len() != program_ir.len() {
+                                self.fuzzer.log(&format!(
+                                    "Liveness pruning: {} -> {} bytes",
+                                    program_ir.len(),
+                                    minimized_ir.len()
+                                ));
+                            }
                             update_stats(unsafe { NUM_WORKERS }, 0, 0, WorkerState::Minimizing, self.fuzzer.corpus.entries.len() as i32);
-                            (minimized_ir_list, minimized_js_list) = (Vec::new(), Vec::new());
-
 
-                            // sort the minimized_js_list by length
-                            minimized_js_list.sort_by_key(|js: &String| js.len());
-                            minimized_ir_list.sort_by_key(|ir: &String| ir.len());
-                            let mut is_maintained: bool = false;
-                            // let mut is_new_coverage: bool = false;
-                            let mut minimized_js_final = String::new();
-                            let mut minimized_ir_final = String::new();
-                            // println!("Js code from client: {}", js_code);
-                            reset_edge_set(self.fuzzer.worker_id as usize, &mut new_edges);
-                            // (is_maintained, is_new_coverage) = unsafe { maintain_coverage_with_mutated_edges(&js_code, self.fuzzer.worker_id as usize, &new_edges) };
-                            // println!("is_maintained: {}", is_maintained);
-                            // println!("is_new_coverage: {}", is_new_coverage);
+                            // ddmin over the JS source, using the edges this
+                            // input just triggered as the coverage oracle.
                             update_stats(unsafe { NUM_WORKERS }, 0, 0, WorkerState::Maintaining, self.fuzzer.corpus.entries.len() as i32);
-                            for (minimized_ir, minimized_js) in minimized_ir_list.iter().zip(minimized_js_list.iter()) {
-                                // println!("minimized_js: {}", minimized_js);
-                                if minimized_js.is_empty() {
-                                    continue;
-                                }
-                                (is_maintained, _) =  maintain_coverage_with_mutated_edges(minimized_js, self.fuzzer.worker_id as usize, &new_edges) ;
-                                if is_maintained  {
-                                    minimized_js_final = minimized_js.clone();
-                                    minimized_ir_final = minimized_ir.clone();
-                                    break;
-                                }
-                            }
+                            reset_edge_set(self.fuzzer.worker_id as usize, &mut new_edges);
+                            let minimized_js = minimize(&js_code, self.fuzzer.worker_id as usize, &new_edges);
                             mark_edge_set(self.fuzzer.worker_id as usize, &mut new_edges);
+
+                            let is_maintained = minimized_js.len() < js_code.len();
+                            let minimized_js_final = minimized_js;
+                            let minimized_ir_final = minimized_ir;
                             if is_maintained  {
 
                                 update_stats(unsafe { NUM_WORKERS }, result, new_cov as i32, WorkerState::Generating, self.fuzzer.corpus.entries.len() as i32);
@@ -1281,7 +1952,17 @@ impl Master {
                                         self.fuzzer.log(&format!("Failed to send to worker: {}", e));
                                     }
                                 }
-                                self.fuzzer.corpus.add_entry(CorpusEntry::new(minimized_ir_final, minimized_js_final));
+                                if let Some(ref to_network) = self.to_network {
+                                    let _ = to_network.send(NetPayload::NewCorpus {
+                                        program_ir: minimized_ir_final.clone(),
+                                        js_code: minimized_js_final.clone(),
+                                    });
+                                }
+                                self.fuzzer.corpus.add_entry_with_lineage(
+                                    CorpusEntry::new(minimized_ir_final, minimized_js_final),
+                                    parent_index,
+                                    Some(pass.clone()),
+                                );
                             }
                             else {
                                 update_stats(unsafe { NUM_WORKERS }, result, new_cov as i32, WorkerState::Generating, self.fuzzer.corpus.entries.len() as i32);
@@ -1300,15 +1981,29 @@ impl Master {
                                         self.fuzzer.log(&format!("Failed to send to worker: {}", e));
                                     }
                                 }
-                                self.fuzzer.corpus.add_entry(CorpusEntry::new(program_ir, js_code));
+                                if let Some(ref to_network) = self.to_network {
+                                    let _ = to_network.send(NetPayload::NewCorpus {
+                                        program_ir: program_ir.clone(),
+                                        js_code: js_code.clone(),
+                                    });
+                                }
+                                self.fuzzer.corpus.add_entry_with_lineage(
+                                    CorpusEntry::new(program_ir, js_code),
+                                    parent_index,
+                                    Some(pass.clone()),
+                                );
                             }
 
-                           
-                           
+
+
                         }
                         update_stats(unsafe { NUM_WORKERS }, result, 0 , WorkerState::Idle, self.fuzzer.corpus.entries.len() as i32);
 
                         
+                    }
+                    Ok(WorkerMessage::Heartbeat { state, exec_count }) => {
+                        consecutive_errors = 0;
+                        self.record_heartbeat(worker_id, state, exec_count);
                     }
                     Ok(WorkerMessage::Crash {
                         program_ir,
@@ -1320,6 +2015,9 @@ impl Master {
                             &js_code,
                             &program_ir,
                         )?;
+                        if let Some(ref to_network) = self.to_network {
+                            let _ = to_network.send(NetPayload::Crash { program_ir, js_code });
+                        }
                     }
                     Err(std::sync::mpsc::TryRecvError::Empty) => {
                         // No message available, this is normal
@@ -1336,12 +2034,51 @@ impl Master {
                 }
             }
             
+            // Classify every worker Active/Idle/Dead and respawn any Dead ones
+            self.supervise_workers();
+
+            // Refresh the snapshot served by the admin/metrics HTTP endpoints
+            self.publish_metrics();
+
             // Check for new AST files
             self.check_new_ast_files()?;
-            
+
+            // Drain any corpus/crash entries pushed in by remote peers
+            self.check_network_inbound();
+
             std::thread::sleep(Duration::from_millis(100));
         }
     }
+
+    /// Accept corpus entries synced in from other `rustrunner` instances and
+    /// fan them out to local workers exactly like a locally-discovered entry.
+    fn check_network_inbound(&mut self) {
+        let Some(ref from_network) = self.from_network else {
+            return;
+        };
+        while let Ok(payload) = from_network.try_recv() {
+            match payload {
+                NetPayload::NewCorpus { program_ir, js_code } => {
+                    self.fuzzer.log("Accepted corpus entry from remote peer");
+                    for tx in &self.to_workers {
+                        if let Err(e) = tx.send(MasterMessage::NewCorpus {
+                            program_ir: program_ir.clone(),
+                            js_code: js_code.clone(),
+                        }) {
+                            self.fuzzer.log(&format!("Failed to send remote entry to worker: {}", e));
+                        }
+                    }
+                    self.fuzzer.corpus.add_entry(CorpusEntry::new(program_ir, js_code));
+                }
+                NetPayload::Crash { program_ir, js_code } => {
+                    self.fuzzer.log("Accepted crash report from remote peer");
+                    if let Err(e) = self.fuzzer.save_crash(&js_code, &program_ir) {
+                        self.fuzzer.log(&format!("Failed to save remote crash: {}", e));
+                    }
+                }
+            }
+        }
+    }
 }
 
 // Function to clean up terminal state on exit
@@ -1358,7 +2095,8 @@ fn cleanup_terminal() {
 fn test_mode() {
     init_reprl_safe(0);
     let js_code = "console.log('Hello, world!');";
-    v8_reprl_check(0);
+    let profile = std::env::var("PROFILE").unwrap_or_else(|_| "v8".to_string());
+    engine_profile::run_reprl_checks(&profile, 0);
     for i in 0..100 {
         let result = unsafe {
             execute_script(
@@ -1390,6 +2128,42 @@ fn test_mode() {
         println!("New cov {}: {} {} {} ", i, new_cov, is_maintained, is_maintained2);
     }
 }
+/// Loads every `.js` file under `dir` and runs them through
+/// `runner::run_tests`, reporting via whichever `--runner-reporter` was
+/// selected. Exits the process instead of returning to the fuzzing loop.
+fn run_test_suite(dir: &PathBuf, seed: u64, worker_count: usize, reporter_name: &str) -> io::Result<()> {
+    let mut test_cases = Vec::new();
+    for (id, file) in fs::read_dir(dir)?.enumerate() {
+        let path = file?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("js") {
+            continue;
+        }
+        let code = fs::read_to_string(&path)?;
+        let mut test_case = TestCase {
+            id: id as u32,
+            filename: path.file_name().map(|n| n.to_string_lossy().into_owned()),
+            code: Some(code),
+            state: None,
+            expectation: None,
+        };
+        test_case.resolve_expectation();
+        test_cases.push(test_case);
+    }
+
+    let config = runner::RunnerConfig {
+        worker_count,
+        seed,
+        ..runner::RunnerConfig::default()
+    };
+
+    match reporter_name {
+        "dot" => { runner::run_tests(test_cases, config, &mut runner::DotReporter::new()); }
+        "json" => { runner::run_tests(test_cases, config, &mut runner::JsonReporter); }
+        _ => { runner::run_tests(test_cases, config, &mut runner::PrettyReporter); }
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Create a Python worker
@@ -1399,10 +2173,45 @@ async fn main() -> Result<()> {
         NUM_WORKERS = opt.num_workers;
     }
     init_stats();
+    if let Some(path) = &opt.engine_profiles {
+        if let Err(e) = engine_profile::load_registry_from_file(path) {
+            eprintln!("Failed to load --engine-profiles {}: {}", path.display(), e);
+        }
+    }
     if opt.test_mode {
         test_mode();
         return Ok(());
     }
+    if let Some(ref dir) = opt.run_tests_dir {
+        run_test_suite(dir, opt.runner_seed, opt.runner_workers, &opt.runner_reporter)?;
+        return Ok(());
+    }
+    if opt.feedback_loop {
+        let config = feedback_loop::FeedbackLoopConfig {
+            worker_id: 0,
+            output_dir: opt.output_dir.clone(),
+            timeout: Duration::from_millis(opt.timeout.max(0) as u64),
+            max_iterations: opt.feedback_iterations,
+            max_duration: opt.feedback_duration_secs.map(Duration::from_secs),
+            mutate_ratio: opt.feedback_mutate_ratio,
+            min_statements: 5,
+            max_statements: 20,
+            seed: opt.feedback_seed,
+        };
+        let mut loop_runner = match feedback_loop::FeedbackLoop::new(config) {
+            Ok(loop_runner) => loop_runner,
+            Err(e) => {
+                eprintln!("Failed to start feedback loop: {}", e);
+                return Ok(());
+            }
+        };
+        let metrics = loop_runner.run();
+        println!(
+            "Feedback loop finished: {} iterations, {} edges, {} corpus entries, {} crashes, {:.1} execs/sec",
+            metrics.iterations, metrics.total_edges, metrics.corpus_size, metrics.crashes_found, metrics.execs_per_sec
+        );
+        return Ok(());
+    }
     // Set up terminal cleanup on exit
     let use_tui = std::env::var("SCROLL_LOG").unwrap_or_else(|_| "1".to_string()) != "0";
     if use_tui {
@@ -1421,80 +2230,28 @@ async fn main() -> Result<()> {
     println!("Starting {} workers...", num_workers);
 
 
-               let (tx_dummy_worker, _): (Sender<WorkerMessage>, Receiver<WorkerMessage>) = channel();
-               let (_, rx_dummy_master): (Sender<MasterMessage>, Receiver<MasterMessage>) = channel();
     let config = Config::new()?;
-    
+
     let mut master = Master::new(&config, num_workers).await?;
-    
-    // Get a clone of master's corpus for workers
-    let master_corpus = master.get_corpus_clone();
-    
-    // Spawn worker threads
-    let mut handles = Vec::new();
+    master.enable_network_sync(&opt);
+    master.enable_metrics_server(&opt);
+    master.configure_cores(&opt);
 
-    for worker_id in 0..num_workers {
-        let (tx_worker, rx_master) = channel();
-        let (tx_master, rx_worker) = channel();
-        // Store channels in master
-        master.from_workers[worker_id] = rx_master;
-        master.to_workers[worker_id] = tx_master;
+    // Master now owns worker spawning (and respawning, on a detected-Dead
+    // heartbeat timeout) so both paths share one code path.
+    master.spawn_all_workers();
 
-        let worker_config = config.clone();
-        let mut worker_corpus = master_corpus.clone(); // Clone master's corpus for each worker
-        worker_corpus.worker_id = worker_id;
-        let handle = std::thread::spawn(move || {
-            // Initialize worker's REPRL
-            println!("Initializing worker {}", worker_id);
-            init_reprl_safe(worker_id);
-            
-            let mut fuzzer = match futures::executor::block_on(Fuzzer::new(
-                &worker_config,
-                worker_id,
-                tx_worker,
-                rx_worker,
-            )) {
-                Ok(mut fuzzer) => {
-                    // Set the corpus from master
-                    fuzzer.set_corpus(worker_corpus);
-                    fuzzer
-                },
-                Err(e) => {
-                    eprintln!("Worker {} initialization failed: {}", worker_id, e);
-                    return;
-                }
-            };
-         
-            println!("Worker {} initialized", worker_id);
-            
-            // Use the fuzz method for the main fuzzing loop
-            if let Err(e) = fuzzer.fuzz() {
-                eprintln!("Worker {} exited with error: {}", worker_id, e);
-            }
-        });
-        
-        handles.push(handle);
-        
-    }
-      // Run master in a separate thread
-      let master_handle = std::thread::spawn(move || {
+    // Run master in a separate thread; it outlives the workers it supervises.
+    let master_handle = std::thread::spawn(move || {
         if let Err(e) = master.run() {
             eprintln!("Master error: {}", e);
         }
     });
 
-    // Wait for all workers to finish
-    for (i, handle) in handles.into_iter().enumerate() {
-        if let Err(e) = handle.join() {
-            eprintln!("Worker {} panicked: {:?}", i, e);
-        }
-    }
-
-    // Wait for master
     if let Err(e) = master_handle.join() {
         eprintln!("Master panicked: {:?}", e);
     }
-    
+
     cleanup_terminal();
     Ok(())
-} 
\ No newline at end of file
+}
\ No newline at end of file
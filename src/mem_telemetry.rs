@@ -0,0 +1,27 @@
+use std::io;
+
+use tikv_jemalloc_ctl::{epoch, stats};
+
+/// Point-in-time snapshot of process memory as seen by jemalloc's own
+/// introspection API, rather than inferred from corpus bookkeeping --
+/// `resident_bytes` is what the OS actually has mapped in, `allocated_bytes`
+/// is what's currently handed out to the application.
+#[derive(Debug, Clone, Copy)]
+pub struct MemorySample {
+    pub resident_bytes: u64,
+    pub allocated_bytes: u64,
+}
+
+fn ctl_err(e: tikv_jemalloc_ctl::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e.to_string())
+}
+
+/// Advances jemalloc's stats epoch (the counters below only update when this
+/// is bumped) and reads resident/allocated bytes. Called on demand rather
+/// than cached, since retention decisions need a fresh number.
+pub fn sample() -> io::Result<MemorySample> {
+    epoch::mib().map_err(ctl_err)?.advance().map_err(ctl_err)?;
+    let resident_bytes = stats::resident::mib().map_err(ctl_err)?.read().map_err(ctl_err)? as u64;
+    let allocated_bytes = stats::allocated::mib().map_err(ctl_err)?.read().map_err(ctl_err)? as u64;
+    Ok(MemorySample { resident_bytes, allocated_bytes })
+}
@@ -0,0 +1,203 @@
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+
+/// One worker's last-reported state, mirrored from `Master::worker_health`
+/// so the HTTP thread never touches the master's own fields directly.
+#[derive(Clone, Serialize)]
+pub struct WorkerSnapshot {
+    pub worker_id: usize,
+    pub state: String,
+    pub exec_count: u64,
+    pub restarts: u32,
+}
+
+/// Per-pass counters mirrored from the cross-worker table `passes::all_stats` returns.
+#[derive(Clone, Serialize)]
+pub struct PassSnapshot {
+    pub name: String,
+    pub execution_count: u64,
+    pub success_count: u64,
+    pub new_coverage: u64,
+    pub new_edges: u64,
+    pub timeout_count: u64,
+    pub error_count: u64,
+}
+
+/// Trimmed-down corpus entry for the `/corpus` listing; full `js_code` is
+/// left out since it's already on disk and would bloat the response.
+#[derive(Clone, Serialize)]
+pub struct CorpusEntrySummary {
+    pub index: u32,
+    pub js_code_len: usize,
+    pub coverage_found: u32,
+    pub is_favored: bool,
+    pub discovered_by_pass: Option<String>,
+}
+
+#[derive(Clone, Serialize, Default)]
+pub struct Snapshot {
+    pub corpus_size: usize,
+    pub edges_covered: u64,
+    pub total_executions: u64,
+    pub total_crashes: u64,
+    pub total_timeouts: u64,
+    pub uptime_secs: u64,
+    pub workers: Vec<WorkerSnapshot>,
+    pub passes: Vec<PassSnapshot>,
+    pub corpus_entries: Vec<CorpusEntrySummary>,
+}
+
+fn state() -> &'static Mutex<Snapshot> {
+    static STATE: OnceLock<Mutex<Snapshot>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(Snapshot::default()))
+}
+
+/// Called by the master at most once every `METRICS_PUBLISH_INTERVAL` (2 s,
+/// throttled in `publish_metrics`) so the `/metrics`,
+/// `/status` and `/corpus` endpoints always reflect a recent (if not
+/// perfectly up-to-the-tick) view of the campaign.
+pub fn publish(snapshot: Snapshot) {
+    if let Ok(mut guard) = state().lock() {
+        *guard = snapshot;
+    }
+}
+
+fn current() -> Snapshot {
+    state().lock().map(|guard| guard.clone()).unwrap_or_default()
+}
+
+fn render_prometheus(s: &Snapshot) -> String {
+    let mut out = String::new();
+    out.push_str("# HELP rustrunner_corpus_size Number of entries in the corpus\n");
+    out.push_str("# TYPE rustrunner_corpus_size gauge\n");
+    out.push_str(&format!("rustrunner_corpus_size {}\n", s.corpus_size));
+
+    out.push_str("# HELP rustrunner_edges_covered Total edges covered so far\n");
+    out.push_str("# TYPE rustrunner_edges_covered gauge\n");
+    out.push_str(&format!("rustrunner_edges_covered {}\n", s.edges_covered));
+
+    out.push_str("# HELP rustrunner_executions_total Total executions across all workers\n");
+    out.push_str("# TYPE rustrunner_executions_total counter\n");
+    out.push_str(&format!("rustrunner_executions_total {}\n", s.total_executions));
+
+    out.push_str("# HELP rustrunner_crashes_total Total crashes found\n");
+    out.push_str("# TYPE rustrunner_crashes_total counter\n");
+    out.push_str(&format!("rustrunner_crashes_total {}\n", s.total_crashes));
+
+    out.push_str("# HELP rustrunner_timeouts_total Total timeouts hit\n");
+    out.push_str("# TYPE rustrunner_timeouts_total counter\n");
+    out.push_str(&format!("rustrunner_timeouts_total {}\n", s.total_timeouts));
+
+    out.push_str("# HELP rustrunner_uptime_seconds Seconds since the master started\n");
+    out.push_str("# TYPE rustrunner_uptime_seconds counter\n");
+    out.push_str(&format!("rustrunner_uptime_seconds {}\n", s.uptime_secs));
+
+    out.push_str("# HELP rustrunner_worker_exec_count Executions reported by each worker's heartbeat\n");
+    out.push_str("# TYPE rustrunner_worker_exec_count counter\n");
+    for w in &s.workers {
+        out.push_str(&format!(
+            "rustrunner_worker_exec_count{{worker=\"{}\",state=\"{}\"}} {}\n",
+            w.worker_id, w.state, w.exec_count
+        ));
+    }
+
+    out.push_str("# HELP rustrunner_worker_restarts_total Times each worker has been respawned after a missed heartbeat\n");
+    out.push_str("# TYPE rustrunner_worker_restarts_total counter\n");
+    for w in &s.workers {
+        out.push_str(&format!(
+            "rustrunner_worker_restarts_total{{worker=\"{}\"}} {}\n",
+            w.worker_id, w.restarts
+        ));
+    }
+
+    out.push_str("# HELP rustrunner_pass_executions_total Executions attributed to each mutation pass\n");
+    out.push_str("# TYPE rustrunner_pass_executions_total counter\n");
+    for p in &s.passes {
+        out.push_str(&format!(
+            "rustrunner_pass_executions_total{{pass=\"{}\"}} {}\n",
+            p.name, p.execution_count
+        ));
+    }
+
+    out.push_str("# HELP rustrunner_pass_new_edges_total New edges attributed to each mutation pass\n");
+    out.push_str("# TYPE rustrunner_pass_new_edges_total counter\n");
+    for p in &s.passes {
+        out.push_str(&format!(
+            "rustrunner_pass_new_edges_total{{pass=\"{}\"}} {}\n",
+            p.name, p.new_edges
+        ));
+    }
+
+    out
+}
+
+fn respond(mut stream: TcpStream, status: &str, content_type: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+fn handle_connection(stream: TcpStream) {
+    let mut reader = match stream.try_clone() {
+        Ok(cloned) => BufReader::new(cloned),
+        Err(_) => return,
+    };
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+
+    match path {
+        "/metrics" => respond(
+            stream,
+            "200 OK",
+            "text/plain; version=0.0.4",
+            &render_prometheus(&current()),
+        ),
+        "/status" => {
+            let body = serde_json::to_string_pretty(&current()).unwrap_or_else(|_| "{}".to_string());
+            respond(stream, "200 OK", "application/json", &body);
+        }
+        "/corpus" => {
+            let body = serde_json::to_string_pretty(&current().corpus_entries)
+                .unwrap_or_else(|_| "[]".to_string());
+            respond(stream, "200 OK", "application/json", &body);
+        }
+        _ => respond(stream, "404 Not Found", "text/plain", "not found"),
+    }
+}
+
+/// Starts the admin/metrics HTTP server on a background thread: Prometheus
+/// text format at `/metrics`, a JSON status blob at `/status`, and the
+/// current corpus listing at `/corpus`. Campaign state is fed in via
+/// `publish`, called at most once every `METRICS_PUBLISH_INTERVAL`; this thread only
+/// ever reads the last published snapshot, never the live corpus/stats.
+pub fn start(addr: SocketAddr) {
+    let listener = match TcpListener::bind(addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("[metrics] failed to bind {}: {}", addr, e);
+            return;
+        }
+    };
+    println!("[metrics] serving /metrics, /status, /corpus on {}", addr);
+    thread::spawn(move || {
+        for conn in listener.incoming() {
+            match conn {
+                Ok(stream) => {
+                    thread::spawn(move || handle_connection(stream));
+                }
+                Err(e) => eprintln!("[metrics] accept error: {}", e),
+            }
+        }
+    });
+}
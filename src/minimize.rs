@@ -0,0 +1,60 @@
+/// Coverage-preserving test-case minimization (delta debugging / ddmin, per
+/// Zeller & Hildebrandt) over the statement list of a corpus entry's
+/// `js_code`, split on `;` the same way `TruncateStatementsPass` does.
+/// Unlike that pass, which drops a single random trailing chunk and hopes,
+/// this repeatedly halves (then re-grows) the chunk size and keeps whichever
+/// candidate still satisfies `keeps_property`, converging on a 1-minimal
+/// subset of statements that still reproduces the interesting behavior.
+///
+/// `keeps_property` is the test oracle: for this crate that's "does
+/// re-executing the candidate still maintain the coverage we're minimizing
+/// for", via `coverage::maintain_coverage_with_mutated_edges`, but ddmin
+/// itself has no notion of coverage or execution -- it just calls the
+/// closure with each candidate statement list.
+pub fn ddmin(statements: &[String], mut keeps_property: impl FnMut(&[String]) -> bool) -> Vec<String> {
+    let mut current = statements.to_vec();
+    if current.len() < 2 || !keeps_property(&current) {
+        return current;
+    }
+
+    let mut num_chunks = 2usize;
+    while current.len() >= 2 {
+        let chunk_size = (current.len() + num_chunks - 1) / num_chunks;
+        let chunks: Vec<&[String]> = current.chunks(chunk_size).collect();
+
+        let mut reduced_to = None;
+        for skip in 0..chunks.len() {
+            let candidate: Vec<String> = chunks.iter().enumerate()
+                .filter(|(i, _)| *i != skip)
+                .flat_map(|(_, chunk)| chunk.iter().cloned())
+                .collect();
+            if !candidate.is_empty() && keeps_property(&candidate) {
+                reduced_to = Some(candidate);
+                break;
+            }
+        }
+
+        match reduced_to {
+            Some(candidate) => {
+                current = candidate;
+                num_chunks = (num_chunks - 1).max(2);
+            }
+            None => {
+                if num_chunks >= current.len() {
+                    break;
+                }
+                num_chunks = (num_chunks * 2).min(current.len());
+            }
+        }
+    }
+    current
+}
+
+/// Splits `js_code` on `;` (mirroring `TruncateStatementsPass`), runs
+/// `ddmin` against it, and rejoins the surviving statements. Returns the
+/// original string unchanged if it has fewer than two statements or doesn't
+/// satisfy `keeps_property` to begin with.
+pub fn ddmin_js_code(js_code: &str, keeps_property: impl FnMut(&[String]) -> bool) -> String {
+    let statements: Vec<String> = js_code.split(';').map(|s| s.to_string()).collect();
+    ddmin(&statements, keeps_property).join(";")
+}
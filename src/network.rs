@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+// Bits correspond 1:1 to optional subsystems that change what a peer can
+// safely send/receive. Keep this in sync with anything that mutates the
+// corpus/IR on-disk format.
+pub const FEATURE_BYTECODE_COLLECTOR: u32 = 1 << 0;
+pub const FEATURE_GENERATOR_CLIENT: u32 = 1 << 1;
+
+pub const IR_FORMAT_VERSION: u16 = 1;
+pub const COV_MAP_VERSION: u16 = 1;
+
+/// First record exchanged on every connection, in both directions, before
+/// any corpus payload is trusted. A version mismatch aborts the connection;
+/// a feature mismatch just negotiates down to the common subset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Handshake {
+    pub fuzzer_name: String,
+    pub ir_version: u16,
+    pub cov_version: u16,
+    pub feature_bits: u32,
+}
+
+impl Handshake {
+    pub fn new(fuzzer_name: &str, feature_bits: u32) -> Self {
+        Handshake {
+            fuzzer_name: fuzzer_name.to_string(),
+            ir_version: IR_FORMAT_VERSION,
+            cov_version: COV_MAP_VERSION,
+            feature_bits,
+        }
+    }
+
+    pub fn supports(&self, feature: u32) -> bool {
+        self.feature_bits & feature != 0
+    }
+
+    /// Feature bits both sides actually agree on; callers should gate any
+    /// optional subsystem behavior on this rather than either side's raw bits.
+    pub fn negotiate(&self, other: &Handshake) -> u32 {
+        self.feature_bits & other.feature_bits
+    }
+
+    fn is_compatible_with(&self, other: &Handshake) -> bool {
+        self.ir_version == other.ir_version && self.cov_version == other.cov_version
+    }
+}
+
+/// What actually flows between peers once the handshake succeeds. Mirrors
+/// the local `WorkerMessage` variants that carry corpus data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NetPayload {
+    NewCorpus { program_ir: String, js_code: String },
+    Crash { program_ir: String, js_code: String },
+}
+
+fn write_frame<T: Serialize>(stream: &mut TcpStream, value: &T) -> io::Result<()> {
+    let body = serde_json::to_vec(value).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    stream.write_all(&(body.len() as u32).to_be_bytes())?;
+    stream.write_all(&body)?;
+    stream.flush()
+}
+
+/// Frames bigger than this are rejected outright rather than allocated --
+/// `len` comes straight off the wire from a peer that hasn't even passed the
+/// handshake yet, so trusting it unbounded lets any connection to the
+/// listener (bound on 0.0.0.0) force a multi-gigabyte allocation with a
+/// single 4-byte length prefix. No real corpus payload needs anywhere close
+/// to this.
+const MAX_FRAME_SIZE: usize = 64 * 1024 * 1024;
+
+fn read_frame<T: for<'a> Deserialize<'a>>(stream: &mut TcpStream) -> io::Result<T> {
+    let mut len_buf = [0u8; 4];
+    stream.read_exact(&mut len_buf)?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+    if len > MAX_FRAME_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame length {} exceeds max of {} bytes", len, MAX_FRAME_SIZE),
+        ));
+    }
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body)?;
+    serde_json::from_slice(&body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn server_handshake(stream: &mut TcpStream, local: &Handshake) -> io::Result<u32> {
+    let peer: Handshake = read_frame(stream)?;
+    write_frame(stream, local)?;
+    if !local.is_compatible_with(&peer) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "incompatible peer '{}': ir={} cov={} (local ir={} cov={})",
+                peer.fuzzer_name, peer.ir_version, peer.cov_version, local.ir_version, local.cov_version
+            ),
+        ));
+    }
+    Ok(local.negotiate(&peer))
+}
+
+fn client_handshake(stream: &mut TcpStream, local: &Handshake) -> io::Result<u32> {
+    write_frame(stream, local)?;
+    let peer: Handshake = read_frame(stream)?;
+    if !local.is_compatible_with(&peer) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "incompatible peer '{}': ir={} cov={} (local ir={} cov={})",
+                peer.fuzzer_name, peer.ir_version, peer.cov_version, local.ir_version, local.cov_version
+            ),
+        ));
+    }
+    Ok(local.negotiate(&peer))
+}
+
+fn handle_connection(mut stream: TcpStream, local: Handshake, inbound_tx: Sender<NetPayload>) {
+    let negotiated = match server_handshake(&mut stream, &local) {
+        Ok(bits) => bits,
+        Err(e) => {
+            eprintln!("[network] rejected peer connection: {}", e);
+            return;
+        }
+    };
+    println!(
+        "[network] accepted peer connection, negotiated feature bits: {:#x}",
+        negotiated
+    );
+    loop {
+        match read_frame::<NetPayload>(&mut stream) {
+            Ok(payload) => {
+                if inbound_tx.send(payload).is_err() {
+                    break;
+                }
+            }
+            Err(_) => break,
+        }
+    }
+}
+
+/// Starts the distributed corpus sync subsystem: a listener accepting
+/// incoming peers on `local_port`, and a background dispatcher that pushes
+/// everything sent on the returned `Sender` out to every address in `peers`.
+/// Returns (inbound corpus/crash payloads from other nodes, outbound queue
+/// for payloads discovered locally).
+pub fn start(
+    local_port: u16,
+    peers: Vec<String>,
+    feature_bits: u32,
+) -> (Receiver<NetPayload>, Sender<NetPayload>) {
+    let fuzzer_name = format!("rustrunner-{}", std::process::id());
+    let local_handshake = Handshake::new(&fuzzer_name, feature_bits);
+
+    let (inbound_tx, inbound_rx) = channel::<NetPayload>();
+    let listener_handshake = local_handshake.clone();
+    match TcpListener::bind(("0.0.0.0", local_port)) {
+        Ok(listener) => {
+            thread::spawn(move || {
+                for conn in listener.incoming() {
+                    match conn {
+                        Ok(stream) => {
+                            let handshake = listener_handshake.clone();
+                            let tx = inbound_tx.clone();
+                            thread::spawn(move || handle_connection(stream, handshake, tx));
+                        }
+                        Err(e) => eprintln!("[network] accept error: {}", e),
+                    }
+                }
+            });
+        }
+        Err(e) => eprintln!("[network] failed to bind port {}: {}", local_port, e),
+    }
+
+    let (outbound_tx, outbound_rx) = channel::<NetPayload>();
+    thread::spawn(move || {
+        for payload in outbound_rx {
+            for addr in &peers {
+                match TcpStream::connect(addr) {
+                    Ok(mut stream) => {
+                        if let Err(e) = client_handshake(&mut stream, &local_handshake) {
+                            eprintln!("[network] handshake with {} failed: {}", addr, e);
+                            continue;
+                        }
+                        if let Err(e) = write_frame(&mut stream, &payload) {
+                            eprintln!("[network] failed to send to {}: {}", addr, e);
+                        }
+                    }
+                    Err(e) => eprintln!("[network] could not connect to {}: {}", addr, e),
+                }
+            }
+        }
+    });
+
+    (inbound_rx, outbound_tx)
+}
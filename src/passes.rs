@@ -0,0 +1,279 @@
+use crate::corpus::CorpusEntry;
+use crate::dictionary::TokenDictionary;
+use rand::Rng;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+
+/// Per-invocation state handed to a pass so it can read the entry being
+/// mutated without the registry needing to know about `Fuzzer` internals.
+pub struct PassContext<'a> {
+    pub worker_id: usize,
+    pub entry: &'a CorpusEntry,
+}
+
+/// What a pass produces when it actually changes something. `None` means
+/// the pass declined to mutate this entry (e.g. nothing splice-able found).
+#[derive(Clone, Debug)]
+pub struct MutatedProgram {
+    pub program_ir: String,
+    pub js_code: String,
+}
+
+/// A single named mutation strategy. Object-safe so many distinct mutators
+/// can live behind one `Box<dyn MutationPass>` in the registry; `Send + Sync`
+/// so the same boxed pass can be handed to multiple worker threads, and
+/// `clone_box` gives every pass dyn-clone style duplication without needing
+/// `Self: Sized` on the trait itself.
+pub trait MutationPass: Send + Sync {
+    fn name(&self) -> &str;
+    fn mutate(&self, ctx: &mut PassContext) -> Option<MutatedProgram>;
+    fn clone_box(&self) -> Box<dyn MutationPass>;
+}
+
+impl Clone for Box<dyn MutationPass> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Duplicates the entry's JS body verbatim — a minimal but real mutator used
+/// as the registry's default pass until a richer structural mutator lands.
+#[derive(Clone)]
+pub struct DuplicateBodyPass;
+
+impl MutationPass for DuplicateBodyPass {
+    fn name(&self) -> &str {
+        "DuplicateBody"
+    }
+
+    fn mutate(&self, ctx: &mut PassContext) -> Option<MutatedProgram> {
+        if ctx.entry.js_code.is_empty() {
+            return None;
+        }
+        Some(MutatedProgram {
+            program_ir: ctx.entry.program_ir.clone(),
+            js_code: format!("{}\n{}", ctx.entry.js_code, ctx.entry.js_code),
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn MutationPass> {
+        Box::new(self.clone())
+    }
+}
+
+/// Drops a random trailing chunk of statements (split on `;`) to bias
+/// exploration toward smaller variants of known-interesting inputs.
+#[derive(Clone)]
+pub struct TruncateStatementsPass;
+
+impl MutationPass for TruncateStatementsPass {
+    fn name(&self) -> &str {
+        "TruncateStatements"
+    }
+
+    fn mutate(&self, ctx: &mut PassContext) -> Option<MutatedProgram> {
+        let statements: Vec<&str> = ctx.entry.js_code.split(';').collect();
+        if statements.len() < 2 {
+            return None;
+        }
+        let mut rng = rand::thread_rng();
+        let keep = rng.gen_range(1..statements.len());
+        Some(MutatedProgram {
+            program_ir: ctx.entry.program_ir.clone(),
+            js_code: statements[..keep].join(";"),
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn MutationPass> {
+        Box::new(self.clone())
+    }
+}
+
+/// Splices a random entry from a `--dictionary`-loaded `TokenDictionary` in
+/// as a new statement at a random statement boundary. Reliably reaches
+/// engine code paths keyed on specific builtin names (`Array.prototype`,
+/// `-0`, `2**53`, ...) that random generation rarely produces on its own.
+#[derive(Clone)]
+pub struct TokenSplicePass {
+    dictionary: Arc<TokenDictionary>,
+}
+
+impl TokenSplicePass {
+    pub fn new(dictionary: Arc<TokenDictionary>) -> Self {
+        TokenSplicePass { dictionary }
+    }
+}
+
+impl MutationPass for TokenSplicePass {
+    fn name(&self) -> &str {
+        "TokenSplice"
+    }
+
+    fn mutate(&self, ctx: &mut PassContext) -> Option<MutatedProgram> {
+        if self.dictionary.is_empty() || ctx.entry.js_code.is_empty() {
+            return None;
+        }
+        let tokens = self.dictionary.tokens();
+        let mut rng = rand::thread_rng();
+        let token = &tokens[rng.gen_range(0..tokens.len())];
+
+        // Split on ';' (same statement-boundary heuristic as TruncateStatementsPass)
+        // and insert the token as its own statement at a random boundary.
+        let mut statements: Vec<String> = ctx.entry.js_code.split(';').map(|s| s.to_string()).collect();
+        let insert_at = rng.gen_range(0..=statements.len() - 1);
+        statements.insert(insert_at, format!(" {}", token));
+
+        Some(MutatedProgram {
+            program_ir: ctx.entry.program_ir.clone(),
+            js_code: statements.join(";"),
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn MutationPass> {
+        Box::new(self.clone())
+    }
+}
+
+/// Running statistics for one registered pass, replacing the ad-hoc
+/// `update_passes(name, ...)` lookup into a global `Vec<Passes>`.
+#[derive(Default, Clone)]
+pub struct PassStats {
+    pub execution_count: u64,
+    pub success_count: u64,
+    pub new_coverage: u64,
+    pub failure_count: u64,
+    pub timeout_count: u64,
+    pub error_count: u64,
+    pub new_edges: u64,
+    pub last_cov_time: Option<Instant>,
+}
+
+impl PassStats {
+    fn record(&mut self, result_code: crate::coverage::ResultCode, new_cov: i32, new_edges: u64) {
+        use crate::coverage::ResultCode;
+        self.execution_count += 1;
+        match result_code {
+            ResultCode::Success => self.success_count += 1,
+            ResultCode::Crash => self.failure_count += 1,
+            ResultCode::Timeout => self.timeout_count += 1,
+            ResultCode::Error => self.error_count += 1,
+        }
+        if new_cov > 0 {
+            self.new_coverage += 1;
+        }
+        self.new_edges += new_edges;
+        if new_cov > 0 {
+            self.last_cov_time = Some(Instant::now());
+        }
+    }
+}
+
+/// Cross-worker aggregate of `PassStats`, keyed by pass name in first-seen
+/// order. Every worker owns its own `PassRegistry` (so `DISABLED_PASSES` can
+/// gate per-worker enablement independently), but the stats display is
+/// process-wide, so `record_result` writes through to this shared table in
+/// addition to the calling registry's own copy. Replaces the old unguarded
+/// global `static mut PASSES: Vec<Passes>` the same way `coverage`'s
+/// `HITCOUNT_STATE` replaced its unguarded per-worker `Vec` -- one `Mutex`
+/// around the whole table, since updates are a handful of counter bumps and
+/// contention isn't a concern.
+fn shared_stats() -> &'static Mutex<Vec<(String, PassStats)>> {
+    static REGISTRY: OnceLock<Mutex<Vec<(String, PassStats)>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn record_shared(name: &str, result_code: crate::coverage::ResultCode, new_cov: i32, new_edges: u64) {
+    let mut shared = shared_stats().lock().unwrap();
+    match shared.iter_mut().find(|(n, _)| n == name) {
+        Some((_, stats)) => stats.record(result_code, new_cov, new_edges),
+        None => {
+            let mut stats = PassStats::default();
+            stats.record(result_code, new_cov, new_edges);
+            shared.push((name.to_string(), stats));
+        }
+    }
+}
+
+/// Records a result for a name that isn't a registered `MutationPass` (e.g.
+/// "BytecodeNovelty", which is a corpus-admission reason, not a mutator) --
+/// the old `update_passes` could create an entry for any name handed to it,
+/// and this is that same escape hatch, narrowed to just the shared table.
+pub fn record_external_result(name: &str, result: i32, new_cov: i32, new_edges: u64) {
+    record_shared(name, crate::coverage::get_result_code(result), new_cov, new_edges);
+}
+
+/// Snapshot of every pass's stats seen so far across all workers, in
+/// first-seen order -- what `print_passes` renders instead of the old
+/// global `Vec<Passes>`.
+pub fn all_stats() -> Vec<(String, PassStats)> {
+    shared_stats().lock().unwrap().clone()
+}
+
+struct RegisteredPass {
+    pass: Box<dyn MutationPass>,
+    enabled: bool,
+    stats: PassStats,
+}
+
+/// Owns the set of boxed mutation passes for one worker along with their
+/// statistics, so new mutators are added by implementing `MutationPass` and
+/// registering once, instead of threading a new name string through the
+/// whole fuzzing loop.
+pub struct PassRegistry {
+    passes: Vec<RegisteredPass>,
+}
+
+impl PassRegistry {
+    /// Registers the built-in pass set; each worker gets its own registry so
+    /// passes can be enabled/disabled per worker without cross-talk.
+    pub fn new() -> Self {
+        let mut registry = Self { passes: Vec::new() };
+        registry.register(Box::new(DuplicateBodyPass));
+        registry.register(Box::new(TruncateStatementsPass));
+        registry
+    }
+
+    pub fn register(&mut self, pass: Box<dyn MutationPass>) {
+        self.passes.push(RegisteredPass {
+            pass,
+            enabled: true,
+            stats: PassStats::default(),
+        });
+    }
+
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) {
+        if let Some(entry) = self.passes.iter_mut().find(|p| p.pass.name() == name) {
+            entry.enabled = enabled;
+        }
+    }
+
+    /// Runs every enabled pass against `ctx`, returning (pass name, mutation)
+    /// for each pass that actually produced something.
+    pub fn run_all(&self, ctx: &mut PassContext) -> Vec<(String, MutatedProgram)> {
+        self.passes
+            .iter()
+            .filter(|p| p.enabled)
+            .filter_map(|p| p.pass.mutate(ctx).map(|m| (p.pass.name().to_string(), m)))
+            .collect()
+    }
+
+    /// Attributes an execution result to the named pass, replacing the old
+    /// global `update_passes(name, result, new_cov, new_edges)` call sites.
+    /// Updates both this worker's own copy (for `stats_for`) and the
+    /// cross-worker shared table `print_passes` reads from.
+    pub fn record_result(&mut self, name: &str, result: i32, new_cov: i32, new_edges: u64) {
+        let result_code = crate::coverage::get_result_code(result);
+        if let Some(entry) = self.passes.iter_mut().find(|p| p.pass.name() == name) {
+            entry.stats.record(result_code, new_cov, new_edges);
+        }
+        record_shared(name, result_code, new_cov, new_edges);
+    }
+
+    pub fn stats_for(&self, name: &str) -> Option<PassStats> {
+        self.passes.iter().find(|p| p.pass.name() == name).map(|p| p.stats.clone())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.passes.iter().map(|p| p.pass.name()).collect()
+    }
+}
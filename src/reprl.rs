@@ -0,0 +1,120 @@
+use std::ffi::CStr;
+use std::time::Duration;
+
+use crate::coverage::{self, get_result_code, OwnedEdgeSet, ResultCode};
+
+/// One `Reprl::execute` call's outcome: the raw exit status REPRL reported,
+/// its `ResultCode` classification, the engine's captured stdout/stderr, and
+/// the edges this run covered. Coverage is exposed as `OwnedEdgeSet` rather
+/// than a raw `&[u8]` bitmap -- `cov_evaluate`'s native buffer is already an
+/// edge-index list, not a dense map, and `OwnedEdgeSet` is the existing safe
+/// wrapper for it (see `crate::coverage`).
+pub struct ExecResult {
+    pub raw_exit: i32,
+    pub result: ResultCode,
+    pub stdout: String,
+    pub stderr: String,
+    edges: OwnedEdgeSet,
+}
+
+impl ExecResult {
+    pub fn timed_out(&self) -> bool {
+        self.result == ResultCode::Timeout
+    }
+
+    pub fn crashed(&self) -> bool {
+        self.result == ResultCode::Crash
+    }
+
+    /// Edge indices this execution covered.
+    pub fn edges(&self) -> &[u32] {
+        self.edges.as_slice()
+    }
+}
+
+fn fetch_stdout(worker_id: i32) -> String {
+    unsafe {
+        let ptr = crate::reprl_fetch_stdout(worker_id);
+        if ptr.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(ptr).to_string_lossy().into_owned()
+        }
+    }
+}
+
+fn fetch_stderr(worker_id: i32) -> String {
+    unsafe {
+        let ptr = crate::coverage::reprl_fetch_stderr(worker_id);
+        if ptr.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(ptr).to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// Owns one REPRL-backed engine child process and its worker-id slot. The
+/// whole point of REPRL is that `execute` reuses the same child across
+/// thousands of runs via its fast-restart path (`fresh_instance = 0`)
+/// instead of paying fork/exec cost per script; the only unsafe FFI in this
+/// path lives here, so call sites just hold a `Reprl` and call `execute`.
+pub struct Reprl {
+    worker_id: usize,
+    executions: u64,
+}
+
+impl Reprl {
+    /// Spawns the child engine for `worker_id` and brings coverage tracking
+    /// up via the same `init`/`spawn`/`coverage_finish_initialization`
+    /// sequence every other worker-id slot in this crate uses
+    /// (`coverage::init_reprl_safe`).
+    pub fn spawn(worker_id: usize) -> Self {
+        coverage::init_reprl_safe(worker_id);
+        Reprl { worker_id, executions: 0 }
+    }
+
+    pub fn worker_id(&self) -> usize {
+        self.worker_id
+    }
+
+    pub fn executions(&self) -> u64 {
+        self.executions
+    }
+
+    /// Runs `script` against the owned child, with REPRL fast-restart
+    /// (`fresh_instance = 0`) reusing the existing process rather than
+    /// forking a new one. `timeout` is truncated to whole milliseconds and
+    /// clamped to `i32::MAX` for the FFI boundary.
+    pub fn execute(&mut self, script: &str, timeout: Duration) -> ExecResult {
+        let timeout_ms = timeout.as_millis().min(i32::MAX as u128) as i32;
+        let script = format!("{}\0", script);
+        let raw_exit = unsafe {
+            crate::execute_script(script.as_ptr() as *mut i8, timeout_ms, 0, self.worker_id as i32)
+        };
+        self.executions += 1;
+
+        ExecResult {
+            raw_exit,
+            result: get_result_code(raw_exit),
+            stdout: fetch_stdout(self.worker_id as i32),
+            stderr: fetch_stderr(self.worker_id as i32),
+            edges: OwnedEdgeSet::from_native(self.worker_id),
+        }
+    }
+}
+
+impl Drop for Reprl {
+    /// Tears down the child and its shared-memory control/data channels.
+    /// `reprl_destroy_context` releases the REPRL shared memory itself;
+    /// `cleanup_reprl` (already used by `runner::run_tests` at worker
+    /// shutdown) handles the rest of the native-side teardown. Both are
+    /// idempotent against an already-dead child, so this runs unconditionally
+    /// even when `execute` panicked partway through a run.
+    fn drop(&mut self) {
+        unsafe {
+            crate::reprl_destroy_context(self.worker_id);
+            crate::coverage::cleanup_reprl(self.worker_id as i32);
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::VecDeque;
+use std::ffi::CStr;
+use std::io::Write;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::coverage::{get_result_code, init_reprl_safe, ResultCode};
+use crate::expectation::{self, Mismatch};
+use crate::generator_client::TestCase;
+
+/// Knobs for `run_tests`. `seed` drives the shuffle that decides dispatch
+/// order, so two runs with the same seed (and the same test cases) hit
+/// workers in the same order and a failure is reproducible instead of
+/// depending on whichever worker happened to pick it up first.
+pub struct RunnerConfig {
+    pub worker_count: usize,
+    pub seed: u64,
+    pub timeout_ms: i32,
+    /// REPRL worker-id slots this run claims, so it doesn't collide with the
+    /// main fuzzing workers (0..num_workers) or the bytecode collector
+    /// (100 + worker_id); see `CorpusManager::init_bytecode_collector`.
+    pub reprl_worker_base: usize,
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        Self {
+            worker_count: 4,
+            seed: 0,
+            timeout_ms: 1000,
+            reprl_worker_base: 200,
+        }
+    }
+}
+
+/// One executed case's outcome, handed to `Reporter::on_case_result` and
+/// folded into the aggregate `RunSummary`.
+#[derive(Debug, Clone)]
+pub struct CaseResult {
+    pub id: u32,
+    pub filename: Option<String>,
+    pub worker_id: usize,
+    pub result: ResultCode,
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: i32,
+    pub duration: Duration,
+    /// Non-empty when the case carried an `Expectation` and at least one of
+    /// its assertions didn't hold; overrides `result` to `Error` even for a
+    /// case REPRL itself classified as `Success`.
+    pub mismatches: Vec<Mismatch>,
+}
+
+fn result_label(result: &ResultCode) -> &'static str {
+    match result {
+        ResultCode::Success => "pass",
+        ResultCode::Error => "fail",
+        ResultCode::Crash => "crash",
+        ResultCode::Timeout => "timeout",
+    }
+}
+
+/// Aggregate counts and per-worker throughput for a completed run, passed
+/// to `Reporter::on_complete`.
+#[derive(Debug, Default)]
+pub struct RunSummary {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub crashed: usize,
+    pub timed_out: usize,
+    pub elapsed: Duration,
+    pub per_worker_throughput: Vec<(usize, f64)>,
+}
+
+impl RunSummary {
+    fn record(&mut self, result: &CaseResult) {
+        self.total += 1;
+        match result.result {
+            ResultCode::Success => self.passed += 1,
+            ResultCode::Error => self.failed += 1,
+            ResultCode::Crash => self.crashed += 1,
+            ResultCode::Timeout => self.timed_out += 1,
+        }
+    }
+}
+
+/// Consumes run events as they happen, so results can be streamed to a
+/// terminal or machine-consumed instead of only available after the whole
+/// run finishes. Implement this for a new output format instead of adding
+/// another mode flag to `run_tests`.
+pub trait Reporter {
+    fn on_start(&mut self, total: usize);
+    fn on_case_result(&mut self, result: &CaseResult);
+    fn on_complete(&mut self, summary: &RunSummary);
+}
+
+/// Human-readable one-line-per-case output, e.g. for a local terminal run.
+pub struct PrettyReporter;
+
+impl Reporter for PrettyReporter {
+    fn on_start(&mut self, total: usize) {
+        println!("Running {} test case(s)...", total);
+    }
+
+    fn on_case_result(&mut self, result: &CaseResult) {
+        let label = result_label(&result.result).to_uppercase();
+        let name = result.filename.clone().unwrap_or_else(|| result.id.to_string());
+        println!(
+            "[{:>7}] {} (worker {}, {:.1}ms)",
+            label,
+            name,
+            result.worker_id,
+            result.duration.as_secs_f64() * 1000.0
+        );
+        for mismatch in &result.mismatches {
+            println!("    {}", mismatch);
+        }
+    }
+
+    fn on_complete(&mut self, summary: &RunSummary) {
+        println!(
+            "\n{} total, {} passed, {} failed, {} crashed, {} timed out in {:.2}s",
+            summary.total,
+            summary.passed,
+            summary.failed,
+            summary.crashed,
+            summary.timed_out,
+            summary.elapsed.as_secs_f64()
+        );
+        for (worker_id, throughput) in &summary.per_worker_throughput {
+            println!("  worker {}: {:.1} cases/sec", worker_id, throughput);
+        }
+    }
+}
+
+/// One character per case (`.`/`F`/`C`/`T`), like the dot reporters common
+/// to test frameworks -- dense output for a CI log.
+pub struct DotReporter {
+    printed: usize,
+}
+
+impl DotReporter {
+    pub fn new() -> Self {
+        Self { printed: 0 }
+    }
+}
+
+impl Reporter for DotReporter {
+    fn on_start(&mut self, total: usize) {
+        println!("Running {} test case(s)...", total);
+    }
+
+    fn on_case_result(&mut self, result: &CaseResult) {
+        let ch = match result.result {
+            ResultCode::Success => '.',
+            ResultCode::Error => 'F',
+            ResultCode::Crash => 'C',
+            ResultCode::Timeout => 'T',
+        };
+        print!("{}", ch);
+        self.printed += 1;
+        if self.printed % 80 == 0 {
+            println!();
+        }
+        let _ = std::io::stdout().flush();
+    }
+
+    fn on_complete(&mut self, summary: &RunSummary) {
+        println!(
+            "\n{} total, {} passed, {} failed, {} crashed, {} timed out in {:.2}s",
+            summary.total,
+            summary.passed,
+            summary.failed,
+            summary.crashed,
+            summary.timed_out,
+            summary.elapsed.as_secs_f64()
+        );
+    }
+}
+
+/// One JSON object per line (start/case_result/complete events), for
+/// machine consumption -- a CI dashboard or replay tool tailing stdout.
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn on_start(&mut self, total: usize) {
+        println!("{}", serde_json::json!({"event": "start", "total": total}));
+    }
+
+    fn on_case_result(&mut self, result: &CaseResult) {
+        let mismatches: Vec<serde_json::Value> = result
+            .mismatches
+            .iter()
+            .map(|m| serde_json::json!({"field": m.field, "expected": m.expected, "actual": m.actual}))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "event": "case_result",
+                "id": result.id,
+                "filename": result.filename,
+                "worker_id": result.worker_id,
+                "result": result_label(&result.result),
+                "stdout": result.stdout,
+                "stderr": result.stderr,
+                "exit_code": result.exit_code,
+                "duration_ms": result.duration.as_secs_f64() * 1000.0,
+                "mismatches": mismatches,
+            })
+        );
+    }
+
+    fn on_complete(&mut self, summary: &RunSummary) {
+        let per_worker: Vec<serde_json::Value> = summary
+            .per_worker_throughput
+            .iter()
+            .map(|(worker_id, throughput)| serde_json::json!({"worker_id": worker_id, "cases_per_sec": throughput}))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "event": "complete",
+                "total": summary.total,
+                "passed": summary.passed,
+                "failed": summary.failed,
+                "crashed": summary.crashed,
+                "timed_out": summary.timed_out,
+                "elapsed_secs": summary.elapsed.as_secs_f64(),
+                "per_worker_throughput": per_worker,
+            })
+        );
+    }
+}
+
+/// Reads the REPRL fuzzout buffer for `worker_id`, the same accessor
+/// `cleanup_reprl`'s caller elsewhere in the crate relies on staying valid
+/// only until the next `execute_script` call -- copied out immediately into
+/// an owned `String`.
+fn fetch_stdout(worker_id: i32) -> String {
+    unsafe {
+        let ptr = crate::reprl_fetch_stdout(worker_id);
+        if ptr.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(ptr).to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// Same as `fetch_stdout`, but for the child's stderr stream.
+fn fetch_stderr(worker_id: i32) -> String {
+    unsafe {
+        let ptr = crate::coverage::reprl_fetch_stderr(worker_id);
+        if ptr.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(ptr).to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// Shuffles `test_cases` with a `SmallRng` seeded from `config.seed`, then
+/// executes them across `config.worker_count` threads, each owning one
+/// REPRL child engine pulled from `config.reprl_worker_base`. Workers drain
+/// a shared queue so a slow case on one worker doesn't stall the others.
+pub fn run_tests(mut test_cases: Vec<TestCase>, config: RunnerConfig, reporter: &mut dyn Reporter) -> RunSummary {
+    let mut rng = SmallRng::seed_from_u64(config.seed);
+    test_cases.shuffle(&mut rng);
+
+    reporter.on_start(test_cases.len());
+
+    let queue = Arc::new(Mutex::new(VecDeque::from(test_cases)));
+    let (result_tx, result_rx) = mpsc::channel::<CaseResult>();
+    let start = Instant::now();
+
+    let worker_count = config.worker_count.max(1);
+    let mut handles = Vec::with_capacity(worker_count);
+    for worker_index in 0..worker_count {
+        let queue = Arc::clone(&queue);
+        let result_tx = result_tx.clone();
+        let worker_id = config.reprl_worker_base + worker_index;
+        let timeout_ms = config.timeout_ms;
+
+        handles.push(thread::spawn(move || {
+            init_reprl_safe(worker_id);
+            let worker_start = Instant::now();
+            let mut executed = 0usize;
+
+            loop {
+                let case = queue.lock().unwrap().pop_front();
+                let Some(case) = case else { break };
+                let Some(code) = case.code.clone() else { continue };
+
+                let case_start = Instant::now();
+                let script = format!("{}\0", code);
+                let raw_result = unsafe {
+                    crate::execute_script(script.as_ptr() as *mut i8, timeout_ms, 0, worker_id as i32)
+                };
+                let mut result = get_result_code(raw_result);
+                let stdout = fetch_stdout(worker_id as i32);
+                let stderr = fetch_stderr(worker_id as i32);
+                executed += 1;
+
+                let mismatches = match &case.expectation {
+                    Some(expectation) => expectation::check(expectation, raw_result, &stdout, &stderr),
+                    None => Vec::new(),
+                };
+                if !mismatches.is_empty() && result == ResultCode::Success {
+                    result = ResultCode::Error;
+                }
+
+                let _ = result_tx.send(CaseResult {
+                    id: case.id,
+                    filename: case.filename,
+                    worker_id,
+                    result,
+                    stdout,
+                    stderr,
+                    exit_code: raw_result,
+                    duration: case_start.elapsed(),
+                    mismatches,
+                });
+            }
+
+            unsafe {
+                crate::coverage::cleanup_reprl(worker_id as i32);
+            }
+            (worker_id, executed, worker_start.elapsed())
+        }));
+    }
+    drop(result_tx);
+
+    let mut summary = RunSummary::default();
+    while let Ok(case_result) = result_rx.recv() {
+        summary.record(&case_result);
+        reporter.on_case_result(&case_result);
+    }
+
+    let mut per_worker_throughput = Vec::with_capacity(handles.len());
+    for handle in handles {
+        if let Ok((worker_id, executed, elapsed)) = handle.join() {
+            let throughput = if elapsed.as_secs_f64() > 0.0 {
+                executed as f64 / elapsed.as_secs_f64()
+            } else {
+                0.0
+            };
+            per_worker_throughput.push((worker_id, throughput));
+        }
+    }
+
+    summary.elapsed = start.elapsed();
+    summary.per_worker_throughput = per_worker_throughput;
+    reporter.on_complete(&summary);
+    summary
+}
@@ -0,0 +1,122 @@
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+
+/// One structural/semantic defect found in a program IR tree before it is
+/// ever handed to `execute_script` — analogous to a compiler's element
+/// checks, but deliberately shallow: it only catches defects a real engine
+/// would reject outright (or silently mis-execute), not anything requiring
+/// full type inference.
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    /// IR node path, e.g. `$.children[2].children[0]`.
+    pub path: String,
+    pub reason: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.reason)
+    }
+}
+
+/// Validates a generated/mutated program IR before it is dispatched to the
+/// engine, returning the first defect found. Bindings are tracked as an
+/// in-scope set threaded depth-first; since the tree is walked in the same
+/// order it will execute, a binding is always inserted before the subtree
+/// that could read it.
+pub fn validate(program_ir: &str) -> Result<(), ValidationError> {
+    let root: Value = serde_json::from_str(program_ir).map_err(|e| ValidationError {
+        path: "$".to_string(),
+        reason: format!("not valid JSON: {}", e),
+    })?;
+    let mut scope: HashSet<String> = HashSet::new();
+    check_node(&root, "$", &mut scope)
+}
+
+fn check_node(node: &Value, path: &str, scope: &mut HashSet<String>) -> Result<(), ValidationError> {
+    match node.get("type").and_then(|t| t.as_str()) {
+        Some("ArrayLiteral") => check_array_literal(node, path)?,
+        Some("Index") => check_index(node, path)?,
+        Some("Identifier") => check_identifier(node, path, scope)?,
+        _ => {}
+    }
+
+    if let Some(name) = node.get("binds").and_then(|b| b.as_str()) {
+        scope.insert(name.to_string());
+    }
+
+    if let Some(children) = node.get("children").and_then(|c| c.as_array()) {
+        for (i, child) in children.iter().enumerate() {
+            check_node(child, &format!("{}.children[{}]", path, i), scope)?;
+        }
+    }
+    Ok(())
+}
+
+/// Every element of a constant array literal must agree on element type —
+/// a generator/mutator that mixes e.g. numbers and objects in one literal
+/// has produced something no real engine accepts as written.
+fn check_array_literal(node: &Value, path: &str) -> Result<(), ValidationError> {
+    let Some(elements) = node.get("elements").and_then(|e| e.as_array()) else {
+        return Ok(());
+    };
+    let mut element_type: Option<&'static str> = None;
+    for (i, el) in elements.iter().enumerate() {
+        let kind = match el {
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Bool(_) => "bool",
+            Value::Null => "null",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        match element_type {
+            None => element_type = Some(kind),
+            Some(expected) if expected != kind => {
+                return Err(ValidationError {
+                    path: format!("{}.elements[{}]", path, i),
+                    reason: format!("array literal mixes element type '{}' with prior '{}'", kind, expected),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// A constant index into a fixed-size array must fall within bounds; an
+/// out-of-range constant index is a defect the generator/mutator introduced
+/// directly (as opposed to runtime out-of-bounds, which is legitimate fuzz
+/// target behavior and must not be filtered here).
+fn check_index(node: &Value, path: &str) -> Result<(), ValidationError> {
+    let (Some(index), Some(length)) = (
+        node.get("index").and_then(|i| i.as_i64()),
+        node.get("length").and_then(|l| l.as_i64()),
+    ) else {
+        return Ok(());
+    };
+    if index < 0 || index >= length {
+        return Err(ValidationError {
+            path: path.to_string(),
+            reason: format!("constant index {} out of range for fixed-size array of length {}", index, length),
+        });
+    }
+    Ok(())
+}
+
+/// A read of a binding that was never introduced earlier in the walk means
+/// the generator emitted a dangling reference (or a mutation pass spliced a
+/// sub-tree into a scope where its bindings no longer exist).
+fn check_identifier(node: &Value, path: &str, scope: &HashSet<String>) -> Result<(), ValidationError> {
+    let Some(name) = node.get("name").and_then(|n| n.as_str()) else {
+        return Ok(());
+    };
+    if !scope.contains(name) {
+        return Err(ValidationError {
+            path: path.to_string(),
+            reason: format!("reference to undeclared binding '{}'", name),
+        });
+    }
+    Ok(())
+}